@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use solana_program::{
+    ed25519_program, instruction::Instruction, sysvar::instructions::load_instruction_at_checked,
+};
+
+use crate::errors::DiceError;
+
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+// Instruction index meaning "this instruction's own data"
+const SELF_INDEX: u16 = u16::MAX;
+
+#[derive(Debug)]
+pub struct Ed25519Signature<'a> {
+    pub public_key: Pubkey,
+    pub signature: &'a [u8],
+    pub message: &'a [u8],
+}
+
+// Every Ed25519 program instruction in the transaction, wherever it sits
+pub fn ed25519_instructions(ix_sysvar: &AccountInfo) -> Result<Vec<Instruction>> {
+    let mut instructions = Vec::new();
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, ix_sysvar) {
+        if ix.program_id == ed25519_program::ID {
+            require!(ix.accounts.is_empty(), DiceError::Ed25519Accounts);
+            instructions.push(ix);
+        }
+        index += 1;
+    }
+
+    require!(!instructions.is_empty(), DiceError::Ed25519Program);
+    Ok(instructions)
+}
+
+// Parses the signatures of an Ed25519 program instruction. Offsets into other instructions
+// are rejected, since the bytes we read here would not be the ones the precompile checked.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<Ed25519Signature<'_>>> {
+    require!(data.len() >= HEADER_LEN, DiceError::Ed25519DataLength);
+    let count = data[0] as usize;
+    require!(count > 0 && data[1] == 0, DiceError::Ed25519Header);
+    let offsets_end = HEADER_LEN + count * OFFSETS_LEN;
+    require!(data.len() >= offsets_end, DiceError::Ed25519DataLength);
+
+    data[HEADER_LEN..offsets_end]
+        .chunks_exact(OFFSETS_LEN)
+        .map(|offsets| {
+            let field = |i: usize| u16::from_le_bytes([offsets[2 * i], offsets[2 * i + 1]]);
+            // Signature, public key and message instruction indexes
+            require!(
+                [field(1), field(3), field(6)] == [SELF_INDEX; 3],
+                DiceError::Ed25519Accounts
+            );
+
+            let public_key = data_at(data, field(2), PUBKEY_LEN)?;
+            Ok(Ed25519Signature {
+                public_key: Pubkey::new_from_array(public_key.try_into().unwrap()),
+                signature: data_at(data, field(0), SIGNATURE_LEN)?,
+                message: data_at(data, field(4), field(5) as usize)?,
+            })
+        })
+        .collect()
+}
+
+pub fn verify_ed25519_signature(
+    ix_sysvar: &AccountInfo,
+    signers: &[Pubkey],
+    sig: &[u8],
+    message: &[u8],
+) -> Result<()> {
+    for ix in ed25519_instructions(ix_sysvar)? {
+        for signature in parse_ed25519_instruction(&ix.data)? {
+            if signature.signature != sig {
+                continue;
+            }
+            require!(
+                signers.contains(&signature.public_key),
+                DiceError::Ed25519Pubkey
+            );
+            require!(signature.message == message, DiceError::Ed25519Message);
+            return Ok(());
+        }
+    }
+
+    err!(DiceError::Ed25519Signature)
+}
+
+// Verifies one Ed25519 instruction carrying a signature by one of `signers` over each
+// message, in order, and returns the signatures
+pub fn verify_ed25519_batch(
+    ix_sysvar: &AccountInfo,
+    signers: &[Pubkey],
+    messages: &[[u8; 32]],
+) -> Result<Vec<[u8; SIGNATURE_LEN]>> {
+    find_ed25519_batch(&ed25519_instructions(ix_sysvar)?, signers, messages)
+}
+
+// Another Ed25519 instruction with the same signature count may sit in the transaction,
+// so a mismatch only fails the batch once every candidate has been tried
+fn find_ed25519_batch(
+    instructions: &[Instruction],
+    signers: &[Pubkey],
+    messages: &[[u8; 32]],
+) -> Result<Vec<[u8; SIGNATURE_LEN]>> {
+    let mut error = DiceError::Ed25519Signature.into();
+    for ix in instructions {
+        if ix.data.first() != Some(&(messages.len() as u8)) {
+            continue;
+        }
+        match check_ed25519_batch(&ix.data, signers, messages) {
+            Ok(signatures) => return Ok(signatures),
+            Err(err) => error = err,
+        }
+    }
+
+    Err(error)
+}
+
+fn check_ed25519_batch(
+    data: &[u8],
+    signers: &[Pubkey],
+    messages: &[[u8; 32]],
+) -> Result<Vec<[u8; SIGNATURE_LEN]>> {
+    parse_ed25519_instruction(data)?
+        .iter()
+        .zip(messages)
+        .map(|(signature, message)| {
+            require!(
+                signers.contains(&signature.public_key),
+                DiceError::Ed25519Pubkey
+            );
+            require!(
+                signature.message == message.as_slice(),
+                DiceError::Ed25519Message
+            );
+            Ok(signature.signature.try_into().unwrap())
+        })
+        .collect()
+}
+
+fn data_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or(DiceError::Ed25519DataLength.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Instruction data laid out like `new_ed25519_instruction`, one signature per entry
+    fn instruction_data(signatures: &[([u8; 32], [u8; 64], &[u8])]) -> Vec<u8> {
+        let mut offset = HEADER_LEN + signatures.len() * OFFSETS_LEN;
+        let mut data = vec![signatures.len() as u8, 0];
+        let mut body = Vec::new();
+        for (public_key, signature, message) in signatures {
+            let pubkey_offset = offset as u16;
+            let signature_offset = pubkey_offset + PUBKEY_LEN as u16;
+            let message_offset = signature_offset + SIGNATURE_LEN as u16;
+            for field in [
+                signature_offset,
+                SELF_INDEX,
+                pubkey_offset,
+                SELF_INDEX,
+                message_offset,
+                message.len() as u16,
+                SELF_INDEX,
+            ] {
+                data.extend_from_slice(&field.to_le_bytes());
+            }
+            body.extend_from_slice(public_key);
+            body.extend_from_slice(signature);
+            body.extend_from_slice(message);
+            offset += PUBKEY_LEN + SIGNATURE_LEN + message.len();
+        }
+        data.extend_from_slice(&body);
+        data
+    }
+
+    fn ed25519_ix(data: Vec<u8>) -> Instruction {
+        Instruction::new_with_bytes(ed25519_program::ID, &data, vec![])
+    }
+
+    #[test]
+    fn parses_self_referencing_offsets() {
+        let data = instruction_data(&[([1; 32], [2; 64], b"bet message")]);
+        let signatures = parse_ed25519_instruction(&data).unwrap();
+
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0].public_key, Pubkey::new_from_array([1; 32]));
+        assert_eq!(signatures[0].signature, [2; 64]);
+        assert_eq!(signatures[0].message, b"bet message");
+    }
+
+    #[test]
+    fn rejects_malformed_instructions() {
+        let data = instruction_data(&[([1; 32], [2; 64], b"bet message")]);
+
+        // Each instruction index pointing at another instruction
+        for index_at in [4, 8, 14] {
+            let mut other_ix = data.clone();
+            other_ix[index_at..index_at + 2].copy_from_slice(&0u16.to_le_bytes());
+            assert_eq!(
+                parse_ed25519_instruction(&other_ix).unwrap_err(),
+                DiceError::Ed25519Accounts.into()
+            );
+        }
+
+        assert_eq!(
+            parse_ed25519_instruction(&data[..data.len() - 1]).unwrap_err(),
+            DiceError::Ed25519DataLength.into()
+        );
+        assert_eq!(
+            parse_ed25519_instruction(&[0, 0]).unwrap_err(),
+            DiceError::Ed25519Header.into()
+        );
+    }
+
+    #[test]
+    fn batch_skips_unrelated_instructions() {
+        let signer = Pubkey::new_from_array([1; 32]);
+        let messages = [[3; 32], [4; 32]];
+        let unrelated = instruction_data(&[([5; 32], [6; 64], b"a"), ([5; 32], [7; 64], b"b")]);
+        let batch = instruction_data(&[([1; 32], [8; 64], &[3; 32]), ([1; 32], [9; 64], &[4; 32])]);
+
+        let signatures = find_ed25519_batch(
+            &[ed25519_ix(unrelated), ed25519_ix(batch)],
+            &[signer],
+            &messages,
+        )
+        .unwrap();
+        assert_eq!(signatures, [[8; 64], [9; 64]]);
+    }
+
+    #[test]
+    fn batch_rejects_mismatched_messages() {
+        let signer = Pubkey::new_from_array([1; 32]);
+        let messages = [[3; 32], [4; 32]];
+
+        // Signed in the other order
+        let swapped =
+            instruction_data(&[([1; 32], [8; 64], &[4; 32]), ([1; 32], [9; 64], &[3; 32])]);
+        assert_eq!(
+            find_ed25519_batch(&[ed25519_ix(swapped)], &[signer], &messages).unwrap_err(),
+            DiceError::Ed25519Message.into()
+        );
+
+        // Signed by someone else
+        let stranger =
+            instruction_data(&[([2; 32], [8; 64], &[3; 32]), ([2; 32], [9; 64], &[4; 32])]);
+        assert_eq!(
+            find_ed25519_batch(&[ed25519_ix(stranger)], &[signer], &messages).unwrap_err(),
+            DiceError::Ed25519Pubkey.into()
+        );
+
+        // Covering only one of the bets
+        let short = instruction_data(&[([1; 32], [8; 64], &[3; 32])]);
+        assert_eq!(
+            find_ed25519_batch(&[ed25519_ix(short)], &[signer], &messages).unwrap_err(),
+            DiceError::Ed25519Signature.into()
+        );
+    }
+}
@@ -6,13 +6,13 @@ pub enum DiceError {
     BumpError,
     #[msg("Overflow")]
     Overflow,
-    #[msg("Minimum bet is 0.01 Sol")]
+    #[msg("Bet is below the house minimum")]
     MinimumBet,
     #[msg("Maximum bet exceeded")]
     MaximumBet,
-    #[msg("Minimum roll is 2")]
+    #[msg("Roll is below the house minimum")]
     MinimumRoll,
-    #[msg("Maximum roll is 96")]
+    #[msg("Roll is above the house maximum")]
     MaximumRoll,
     #[msg("Timeout not yet reached")]
     TimeoutNotReached,
@@ -29,5 +29,33 @@ pub enum DiceError {
     #[msg("Ed25119 Accounts Error")]
     Ed25519Accounts,
     #[msg("Ed25119 Data Length Error")]
-    Ed25519DataLength
+    Ed25519DataLength,
+    #[msg("Invalid house config")]
+    InvalidConfig,
+    #[msg("Vault balance is reserved for pending bets")]
+    InsufficientFunds,
+    #[msg("Invalid roll range")]
+    InvalidRange,
+    #[msg("VRF proof verification failed")]
+    VrfProof,
+    #[msg("Preimage does not match the committed hash chain")]
+    HashChainPreimage,
+    #[msg("Target slot hash is not in the SlotHashes sysvar")]
+    SlotHashUnavailable,
+    #[msg("Unsupported signed message version")]
+    MessageVersion,
+    #[msg("Remaining accounts must be (bet, player, player stats) triples for this vault")]
+    BatchAccounts,
+    #[msg("Epoch does not match the current leaderboard")]
+    LeaderboardEpoch,
+    #[msg("Leaderboard epoch has not ended yet")]
+    LeaderboardEpochRunning,
+    #[msg("Bet was placed for a different resolution mode")]
+    ResolutionModeMismatch,
+    #[msg("No hash chain has been committed")]
+    HashChainNotCommitted,
+    #[msg("Hash chain still has open bets")]
+    HashChainBetsOpen,
+    #[msg("Hash-chain bets must mix in a future slot hash")]
+    SlotHashRequired
 }
\ No newline at end of file
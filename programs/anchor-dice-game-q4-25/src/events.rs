@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Bet, BetType, ConfigParams, ResolutionMode};
+
+#[event]
+pub struct BetPlaced {
+    pub vault: Pubkey,
+    pub player: Pubkey,
+    // Default pubkey for SOL bets
+    pub mint: Pubkey,
+    pub seed: u128,
+    pub amount: u64,
+    pub roll: u16,
+    pub bet_type: BetType,
+    pub mode: ResolutionMode,
+    // Paid out if the bet wins
+    pub payout: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct BetResolved {
+    pub vault: Pubkey,
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub seed: u128,
+    pub amount: u64,
+    // The number the player bet on, and the number that was rolled
+    pub target: u16,
+    pub bet_type: BetType,
+    pub roll: u16,
+    pub won: bool,
+    // Zero for a losing bet
+    pub payout: u64,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub vault: Pubkey,
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub seed: u128,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VaultFunded {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub vault: Pubkey,
+    pub params: ConfigParams,
+}
+
+#[event]
+pub struct ResolverRotated {
+    pub vault: Pubkey,
+    pub resolver: Pubkey,
+    pub previous_resolver: Pubkey,
+    pub previous_resolver_expiry: u64,
+}
+
+#[event]
+pub struct TokenVaultUpdated {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub min_bet: u64,
+    pub max_bet: u64,
+}
+
+#[event]
+pub struct HashChainCommitted {
+    pub vault: Pubkey,
+    pub tip: [u8; 32],
+}
+
+// `epoch` was archived and the next one began at `slot`
+#[event]
+pub struct LeaderboardRolledOver {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub slot: u64,
+}
+
+impl BetPlaced {
+    pub fn new(vault: Pubkey, bet: &Bet) -> Self {
+        Self {
+            vault,
+            player: bet.player,
+            mint: bet.mint,
+            seed: bet.seed,
+            amount: bet.amount,
+            roll: bet.roll,
+            bet_type: bet.bet_type,
+            mode: bet.mode,
+            payout: bet.payout,
+            slot: bet.slot,
+        }
+    }
+}
+
+impl BetResolved {
+    pub fn new(vault: Pubkey, bet: &Bet, result: u32) -> Self {
+        let won = bet.bet_type.wins(result, bet.roll);
+        Self {
+            vault,
+            player: bet.player,
+            mint: bet.mint,
+            seed: bet.seed,
+            amount: bet.amount,
+            target: bet.roll,
+            bet_type: bet.bet_type,
+            roll: result as u16,
+            won,
+            payout: if won { bet.payout } else { 0 },
+        }
+    }
+}
+
+impl BetRefunded {
+    pub fn new(vault: Pubkey, bet: &Bet) -> Self {
+        Self {
+            vault,
+            player: bet.player,
+            mint: bet.mint,
+            seed: bet.seed,
+            amount: bet.amount,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VaultState;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct CommitHashChain<'info> {
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> CommitHashChain<'info> {
+    pub fn commit_hash_chain(&mut self, tip: [u8; 32]) -> Result<()> {
+        self.vault_state.commit(tip)
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub house: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        let accounts = Transfer {
+            from: self.house.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        transfer(ctx, amount)
+    }
+}
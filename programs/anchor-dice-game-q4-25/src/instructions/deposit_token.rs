@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::transfer_tokens;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct DepositToken<'info> {
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = house,
+        associated_token::token_program = token_program
+    )]
+    pub house_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DepositToken<'info> {
+    pub fn deposit_token(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        transfer_tokens(
+            &self.token_program,
+            &self.house_ata,
+            &self.mint,
+            &self.vault_ata,
+            self.house.to_account_info(),
+            remaining_accounts,
+            amount,
+            &[],
+        )
+    }
+}
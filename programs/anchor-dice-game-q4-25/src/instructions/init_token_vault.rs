@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::state::TokenVault;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct InitTokenVault<'info> {
+    #[account(mut)]
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = house,
+        space = TokenVault::DISCRIMINATOR.len() + TokenVault::INIT_SPACE,
+        seeds = [b"token_vault", vault.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        init_if_needed,
+        payer = house,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTokenVault<'info> {
+    pub fn init_token_vault(
+        &mut self,
+        bumps: &InitTokenVaultBumps,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        self.token_vault.mint = self.mint.key();
+        self.token_vault.bump = bumps.token_vault;
+        self.token_vault.update(min_bet, max_bet)
+    }
+}
@@ -1,19 +1,82 @@
 use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
 
+use crate::state::{
+    ConfigParams, HouseConfig, HouseStats, Leaderboard, RecentResults, VaultState,
+};
+
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(vault_id: u16)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub house: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"vault", house.key().as_ref()],
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = house,
+        space = HouseConfig::DISCRIMINATOR.len() + HouseConfig::INIT_SPACE,
+        seeds = [b"config", vault.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        init,
+        payer = house,
+        space = VaultState::DISCRIMINATOR.len() + VaultState::INIT_SPACE,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        init,
+        payer = house,
+        space = HouseStats::DISCRIMINATOR.len() + HouseStats::INIT_SPACE,
+        seeds = [b"house_stats", vault.key().as_ref()],
+        bump
+    )]
+    pub house_stats: Account<'info, HouseStats>,
+    #[account(
+        init,
+        payer = house,
+        space = Leaderboard::DISCRIMINATOR.len() + std::mem::size_of::<Leaderboard>(),
+        seeds = [b"leaderboard", vault.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        init,
+        payer = house,
+        space = RecentResults::DISCRIMINATOR.len() + std::mem::size_of::<RecentResults>(),
+        seeds = [b"recent_results", vault.key().as_ref()],
+        bump
+    )]
+    pub recent_results: AccountLoader<'info, RecentResults>,
     pub system_program: Program<'info, System>
 }
 
 impl<'info> Initialize<'info> {
+    pub fn init_config(&mut self, bumps: &InitializeBumps, params: &ConfigParams) -> Result<()> {
+        self.config.bump = bumps.config;
+        self.vault_state.bump = bumps.vault_state;
+        self.house_stats.bump = bumps.house_stats;
+        self.config.resolver = self.house.key();
+
+        let mut leaderboard = self.leaderboard.load_init()?;
+        leaderboard.vault = self.vault.key();
+        leaderboard.start_slot = Clock::get()?.slot;
+        leaderboard.bump = bumps.leaderboard;
+
+        let mut recent_results = self.recent_results.load_init()?;
+        recent_results.vault = self.vault.key();
+        recent_results.bump = bumps.recent_results;
+        self.config.update(params)
+    }
+
     pub fn init(&mut self, amount: u64) -> Result<()> {
         let accounts = Transfer {
             from: self.house.to_account_info(),
@@ -27,4 +90,4 @@ impl<'info> Initialize<'info> {
 
         transfer(ctx, amount)
     }
-}
\ No newline at end of file
+}
@@ -7,5 +7,47 @@ pub use place_bet::*;
 pub mod resolve_bet;
 pub use resolve_bet::*;
 
+pub mod resolve_bets;
+pub use resolve_bets::*;
+
 pub mod refund_bet;
-pub use refund_bet::*;
\ No newline at end of file
+pub use refund_bet::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod deposit;
+pub use deposit::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod init_token_vault;
+pub use init_token_vault::*;
+
+pub mod update_token_vault;
+pub use update_token_vault::*;
+
+pub mod deposit_token;
+pub use deposit_token::*;
+
+pub mod withdraw_token;
+pub use withdraw_token::*;
+
+pub mod place_token_bet;
+pub use place_token_bet::*;
+
+pub mod resolve_token_bet;
+pub use resolve_token_bet::*;
+
+pub mod refund_token_bet;
+pub use refund_token_bet::*;
+
+pub mod commit_hash_chain;
+pub use commit_hash_chain::*;
+
+pub mod rotate_resolver;
+pub use rotate_resolver::*;
+
+pub mod rollover_leaderboard;
+pub use rollover_leaderboard::*;
\ No newline at end of file
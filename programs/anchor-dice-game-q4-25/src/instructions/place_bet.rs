@@ -3,10 +3,14 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::{errors::DiceError, state::Bet};
+use crate::{
+    errors::DiceError,
+    state::{Bet, BetType, HouseConfig, PlayerStats, ResolutionMode, VaultState, SLOT_HASH_DELAY},
+};
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(seed:u128)]
+#[instruction(vault_id: u16, seed: u128)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
@@ -14,10 +18,21 @@ pub struct PlaceBet<'info> {
     pub house: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds = [b"vault", house.key().as_ref()],
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
     #[account(
         init,
         payer = player,
@@ -26,31 +41,77 @@ pub struct PlaceBet<'info> {
         bump
     )]
     pub bet: Account<'info, Bet>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerStats::DISCRIMINATOR.len() + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", vault.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> PlaceBet<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_bet(
         &mut self,
         bumps: &PlaceBetBumps,
         seed: u128,
-        roll: u8,
+        roll: u16,
+        bet_type: BetType,
         amount: u64,
+        slot_hash: bool,
+        client_seed: [u8; 32],
+        mode: ResolutionMode,
     ) -> Result<()> {
         // Validate bet parameters
-        require!(amount >= 10_000_000, DiceError::MinimumBet); // 0.01 SOL
-        require!(roll >= 2, DiceError::MinimumRoll);
-        require!(roll <= 96, DiceError::MaximumRoll);
+        require!(amount >= self.config.min_bet, DiceError::MinimumBet);
+        require!(amount <= self.config.max_bet, DiceError::MaximumBet);
+        self.config.check_roll(roll, bet_type)?;
+
+        // The free bankroll must be able to cover the payout if the player wins
+        let payout = self.config.payout(amount, roll, bet_type)?;
+        let free_balance = self.vault_state.free_balance(self.vault.lamports())?;
+        let max_payout = self.config.max_payout(free_balance)?;
+        require!(payout <= max_payout, DiceError::MaximumBet);
+        self.vault_state.lock(payout)?;
+
+        // The house knows every seed of its chain, so without a future slot hash it could steer
+        // a known seed onto this bet by placing its own bets ahead of it
+        let chain_position = match mode {
+            ResolutionMode::HashChain => {
+                require!(slot_hash, DiceError::SlotHashRequired);
+                self.vault_state.take_chain_position()?
+            }
+            _ => 0,
+        };
+
+        let slot = Clock::get()?.slot;
+        let target_slot = if slot_hash { slot + SLOT_HASH_DELAY } else { 0 };
 
         self.bet.set_inner(Bet {
-            slot: Clock::get()?.slot,
+            slot,
+            target_slot,
             player: self.player.key(),
+            mint: Pubkey::default(),
             seed,
+            client_seed,
             roll,
+            bet_type,
+            mode,
+            chain_position,
+            resolver: self.config.resolver,
             amount,
+            payout,
+            locked: payout,
             bump: bumps.bet,
         });
-        Ok(())
+
+        self.player_stats.player = self.player.key();
+        self.player_stats.vault = self.vault.key();
+        self.player_stats.bump = bumps.player_stats;
+        self.player_stats.record_bet(amount)
     }
 
     pub fn deposit(&mut self, amount: u64) -> Result<()> {
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::DiceError,
+    state::{Bet, BetType, HouseConfig, ResolutionMode, TokenVault, VaultState, SLOT_HASH_DELAY},
+    utils::{gross_amount, transfer_fee, transfer_tokens},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16, seed: u128)]
+pub struct PlaceTokenBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    ///CHECK: This is safe
+    pub house: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", vault.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = player,
+        space = Bet::DISCRIMINATOR.len() + Bet::INIT_SPACE,
+        seeds = [b"bet", vault.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceTokenBet<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_bet(
+        &mut self,
+        bumps: &PlaceTokenBetBumps,
+        seed: u128,
+        roll: u16,
+        bet_type: BetType,
+        amount: u64,
+        slot_hash: bool,
+        client_seed: [u8; 32],
+        mode: ResolutionMode,
+    ) -> Result<()> {
+        // Validate bet parameters
+        require!(amount >= self.token_vault.min_bet, DiceError::MinimumBet);
+        require!(amount <= self.token_vault.max_bet, DiceError::MaximumBet);
+        self.config.check_roll(roll, bet_type)?;
+
+        // Only what reaches the vault after the mint's transfer fee is wagered
+        let amount = amount - transfer_fee(&self.mint, amount)?;
+
+        // The free bankroll must be able to cover the payout plus its transfer fee
+        let payout = self.config.payout(amount, roll, bet_type)?;
+        let locked = gross_amount(&self.mint, payout)?;
+        let free_balance = self.token_vault.free_balance(self.vault_ata.amount);
+        let max_payout = self.config.max_payout(free_balance)?;
+        require!(locked <= max_payout, DiceError::MaximumBet);
+        self.token_vault.lock(locked)?;
+
+        // The house knows every seed of its chain, so without a future slot hash it could steer
+        // a known seed onto this bet by placing its own bets ahead of it
+        let chain_position = match mode {
+            ResolutionMode::HashChain => {
+                require!(slot_hash, DiceError::SlotHashRequired);
+                self.vault_state.take_chain_position()?
+            }
+            _ => 0,
+        };
+
+        let slot = Clock::get()?.slot;
+        let target_slot = if slot_hash { slot + SLOT_HASH_DELAY } else { 0 };
+
+        self.bet.set_inner(Bet {
+            slot,
+            target_slot,
+            player: self.player.key(),
+            mint: self.mint.key(),
+            seed,
+            client_seed,
+            roll,
+            bet_type,
+            mode,
+            chain_position,
+            resolver: self.config.resolver,
+            amount,
+            payout,
+            locked,
+            bump: bumps.bet,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        transfer_tokens(
+            &self.token_program,
+            &self.player_ata,
+            &self.mint,
+            &self.vault_ata,
+            self.player.to_account_info(),
+            remaining_accounts,
+            amount,
+            &[],
+        )
+    }
+}
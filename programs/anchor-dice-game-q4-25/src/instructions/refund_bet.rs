@@ -3,9 +3,15 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::{errors::DiceError, state::Bet};
+use crate::{
+    errors::DiceError,
+    instructions::slot_hash_expired,
+    state::{Bet, HouseConfig, HouseStats, PlayerStats, ResolutionMode, VaultState},
+};
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(vault_id: u16)]
 pub struct RefundBet<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
@@ -13,31 +19,69 @@ pub struct RefundBet<'info> {
     pub house: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds = [b"vault", house.key().as_ref()],
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        mut,
+        seeds = [b"house_stats", vault.key().as_ref()],
+        bump = house_stats.bump
+    )]
+    pub house_stats: Account<'info, HouseStats>,
     #[account(
         mut,
         close = player,
+        has_one = player,
+        constraint = bet.mint == Pubkey::default(),
         seeds = [b"bet", vault.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
         bump = bet.bump
     )]
     pub bet: Account<'info, Bet>,
+    /// CHECK: This is the slot hashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player_stats", vault.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> RefundBet<'info> {
-    pub fn refund_bet(&mut self, bumps: &RefundBetBumps) -> Result<()> {
-        let slot = Clock::get()?.slot;
-        require!((slot - self.bet.slot) > 1000, DiceError::TimeoutNotReached);
+    pub fn refund_bet(&mut self, vault_id: u16, bumps: &RefundBetBumps) -> Result<()> {
+        expire_bet(
+            &self.bet,
+            &self.config,
+            &mut self.vault_state,
+            &self.slot_hashes,
+        )?;
+        self.vault_state.release(self.bet.locked)?;
+
         let accounts = Transfer {
             from: self.vault.to_account_info(),
             to: self.player.to_account_info(),
         };
 
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[b"vault", &self.house.key().to_bytes(), &[bumps.vault]]];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            &self.house.key().to_bytes(),
+            &vault_id.to_le_bytes(),
+            &[bumps.vault],
+        ]];
 
         let ctx = CpiContext::new_with_signer(
             self.system_program.to_account_info(),
@@ -45,6 +89,28 @@ impl<'info> RefundBet<'info> {
             signer_seeds,
         );
 
-        transfer(ctx, self.bet.amount)
+        transfer(ctx, self.bet.amount)?;
+        self.house_stats.record_refund(self.bet.amount)?;
+        self.player_stats.record_refund(self.bet.amount)
+    }
+}
+
+// Fails unless the bet has timed out or its target slot hash can never be read, then frees its
+// place in the hash chain
+pub fn expire_bet(
+    bet: &Bet,
+    config: &HouseConfig,
+    vault_state: &mut VaultState,
+    slot_hashes: &AccountInfo,
+) -> Result<()> {
+    // A target slot that was skipped or left the SlotHashes window can never be resolved
+    let target_expired = bet.target_slot != 0 && slot_hash_expired(slot_hashes, bet.target_slot)?;
+    require!(
+        target_expired || (Clock::get()?.slot - bet.slot) > config.refund_timeout,
+        DiceError::TimeoutNotReached
+    );
+    if bet.mode == ResolutionMode::HashChain {
+        vault_state.close_chain_bet()?;
     }
+    Ok(())
 }
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    instructions::expire_bet,
+    state::{Bet, HouseConfig, TokenVault, VaultState},
+    utils::transfer_tokens,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct RefundTokenBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    ///CHECK: This is safe
+    pub house: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", vault.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        has_one = mint,
+        seeds = [b"bet", vault.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    /// CHECK: This is the slot hashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundTokenBet<'info> {
+    pub fn refund_bet(
+        &mut self,
+        vault_id: u16,
+        bumps: &RefundTokenBetBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        expire_bet(
+            &self.bet,
+            &self.config,
+            &mut self.vault_state,
+            &self.slot_hashes,
+        )?;
+        self.token_vault.release(self.bet.locked)?;
+
+        // Return what the vault received for the wager, since nothing beyond the payout was
+        // reserved to cover a fee on the way back
+        let amount = self.bet.amount;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            &self.house.key().to_bytes(),
+            &vault_id.to_le_bytes(),
+            &[bumps.vault],
+        ]];
+
+        transfer_tokens(
+            &self.token_program,
+            &self.vault_ata,
+            &self.mint,
+            &self.player_ata,
+            self.vault.to_account_info(),
+            remaining_accounts,
+            amount,
+            signer_seeds,
+        )
+    }
+}
@@ -1,113 +1,150 @@
-use anchor_instruction_sysvar::Ed25519InstructionSignatures;
 use anchor_lang::{
     prelude::*,
     system_program::{transfer, Transfer},
 };
-use solana_program::hash::hash;
-use solana_program::sysvar::instructions::load_instruction_at_checked;
 
-use crate::{errors::DiceError, state::Bet};
+use crate::{
+    ed25519::verify_ed25519_signature,
+    errors::DiceError,
+    state::{
+        Bet, HouseConfig, HouseStats, Leaderboard, PlayerStats, RecentResults, ResolutionMode,
+        VaultState,
+    },
+    vrf::{verify_vrf, PROOF_LEN},
+};
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(vault_id: u16)]
 pub struct ResolveBet<'info> {
+    // Anyone may submit a resolution, since the house authorises the outcome cryptographically
     #[account(mut)]
-    pub house: Signer<'info>,
+    pub resolver: Signer<'info>,
+    pub house: SystemAccount<'info>,
     #[account(mut)]
     pub player: SystemAccount<'info>,
     #[account(
         mut,
-        seeds = [b"vault", house.key().as_ref()],
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        mut,
+        seeds = [b"house_stats", vault.key().as_ref()],
+        bump = house_stats.bump
+    )]
+    pub house_stats: Account<'info, HouseStats>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard", vault.key().as_ref()],
+        bump = leaderboard.load()?.bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        mut,
+        seeds = [b"recent_results", vault.key().as_ref()],
+        bump = recent_results.load()?.bump
+    )]
+    pub recent_results: AccountLoader<'info, RecentResults>,
     #[account(
         mut,
         close = player,
+        has_one = player,
+        constraint = bet.mint == Pubkey::default(),
         seeds = [b"bet", vault.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
         bump = bet.bump
     )]
     pub bet: Account<'info, Bet>,
+    #[account(
+        mut,
+        seeds = [b"player_stats", vault.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
     /// CHECK: This is the instructions sysvar
     #[account(address = solana_program::sysvar::instructions::id())]
     pub instruction_sysvar: AccountInfo<'info>,
+    /// CHECK: This is the slot hashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> ResolveBet<'info> {
     pub fn verify_ed25519_signature(&self, sig: &[u8]) -> Result<()> {
-        let ix_sysvar = &self.instruction_sysvar;
-
-        // The Ed25519 verify instruction should be at index 0 (prepended before this ix)
-        let ed25519_ix =
-            load_instruction_at_checked(0, ix_sysvar).map_err(|_| DiceError::Ed25519Header)?;
-
-        // Verify the instruction is actually for the Ed25519 program
-        require_keys_eq!(
-            ed25519_ix.program_id,
-            solana_program::ed25519_program::id(),
-            DiceError::Ed25519Program
-        );
-
-        // Unpack the Ed25519 instruction data
-        let signatures = Ed25519InstructionSignatures::unpack(&ed25519_ix.data)
-            .map_err(|_| DiceError::Ed25519Header)?;
-
-        let signature = &signatures.0.first().ok_or(DiceError::Ed25519Signature)?;
-
-        // Verify the signing pubkey matches the house
-        let signer = signature.public_key.ok_or(DiceError::Ed25519Pubkey)?;
-
-        require_keys_eq!(signer, self.house.key(), DiceError::Ed25519Pubkey);
-
-        // Verify the signature matches
-        let sig_bytes = signature.signature.ok_or(DiceError::Ed25519Signature)?;
-
-        require!(sig_bytes.as_ref() == sig, DiceError::Ed25519Signature);
+        self.bet.require_mode(ResolutionMode::Signature)?;
+        verify_ed25519_signature(
+            &self.instruction_sysvar,
+            &self.config.resolvers(Clock::get()?.slot),
+            sig,
+            &self.bet.digest(&self.vault.key()),
+        )
+    }
 
-        // Verify the message matches the bet data
-        let msg = signature
-            .message
-            .as_ref()
-            .ok_or(DiceError::Ed25519Message)?;
+    pub fn verify_vrf_proof(&self, proof: &[u8; PROOF_LEN]) -> Result<[u8; 64]> {
+        verify_vrf_proof(&self.bet, &self.vault.key(), proof)
+    }
 
-        require!(*msg == self.bet.to_slice(), DiceError::Ed25519Message);
+    pub fn reveal_server_seed(&mut self, preimage: &[u8; 32]) -> Result<Vec<u8>> {
+        reveal_server_seed(&self.bet, &mut self.vault_state, preimage)
+    }
 
-        Ok(())
+    pub fn mix_entropy(&self, server_seed: Vec<u8>) -> Result<Vec<u8>> {
+        mix_entropy(&self.bet, &self.slot_hashes, server_seed)
     }
 
-    pub fn resolve_bet(&mut self, sig: &[u8], bumps: &ResolveBetBumps) -> Result<()> {
-        let hash_result = hash(sig).to_bytes();
-        let mut hash_ref = hash_result.as_ref();
-        let mut result: u128 = 0;
-        loop {
-            if hash_ref.is_empty() {
-                break;
-            }
-            let (value, rest) = hash_ref.split_at(16);
-            hash_ref = rest;
-            result = result.wrapping_add(u128::from_le_bytes(value.try_into().unwrap()));
-        }
+    pub fn resolve_bet(
+        &mut self,
+        result: u32,
+        vault_id: u16,
+        bumps: &ResolveBetBumps,
+    ) -> Result<()> {
+        self.vault_state.release(self.bet.locked)?;
+        pay_resolver_reward(
+            &self.bet.to_account_info(),
+            &self.resolver,
+            self.config.resolver_reward,
+        )?;
 
-        // Map the result to 1-100 range
-        let result = (result % 100) + 1;
+        let won = self.bet.bet_type.wins(result, self.bet.roll);
+        let payout = if won { self.bet.payout } else { 0 };
+        self.house_stats
+            .record_resolution(self.bet.amount, payout, result)?;
 
-        if result > self.bet.roll.into() {
-            // Player wins — calculate payout
-            let payout = (self.bet.amount as u128)
-                .checked_mul(10000)
-                .ok_or(DiceError::Overflow)?
-                .checked_div(self.bet.roll as u128)
-                .ok_or(DiceError::Overflow)?
-                .checked_div(100)
-                .ok_or(DiceError::Overflow)? as u64;
+        let mut leaderboard = self.leaderboard.load_mut()?;
+        self.player_stats
+            .record_epoch(leaderboard.epoch, self.bet.amount, payout)?;
+        leaderboard.record(&self.player_stats);
+        drop(leaderboard);
+        self.recent_results
+            .load_mut()?
+            .push(&self.bet, result, Clock::get()?.slot);
 
+        if won {
+            // Player wins — pay out the amount reserved at placement
             let accounts = Transfer {
                 from: self.vault.to_account_info(),
                 to: self.player.to_account_info(),
             };
 
-            let signer_seeds: &[&[&[u8]]] =
-                &[&[b"vault", &self.house.key().to_bytes(), &[bumps.vault]]];
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                &self.house.key().to_bytes(),
+                &vault_id.to_le_bytes(),
+                &[bumps.vault],
+            ]];
 
             let ctx = CpiContext::new_with_signer(
                 self.system_program.to_account_info(),
@@ -115,9 +152,124 @@ impl<'info> ResolveBet<'info> {
                 signer_seeds,
             );
 
-            transfer(ctx, payout)?;
+            transfer(ctx, self.bet.payout)?;
+            self.player_stats.record_win(self.bet.payout)
+        } else {
+            self.player_stats.record_loss();
+            Ok(())
         }
+    }
+}
+
+// Pays the resolver out of the bet rent, the rest goes back to the player when the bet closes
+pub fn pay_resolver_reward(bet: &AccountInfo, resolver: &AccountInfo, reward: u64) -> Result<()> {
+    let reward = reward.min(bet.lamports());
+    bet.sub_lamports(reward)?;
+    resolver.add_lamports(reward)?;
+    Ok(())
+}
+
+// Only the key the bet was placed under is checked, so a rotation can't offer a second outcome
+pub fn verify_vrf_proof(bet: &Bet, vault: &Pubkey, proof: &[u8; PROOF_LEN]) -> Result<[u8; 64]> {
+    bet.require_mode(ResolutionMode::Vrf)?;
+    let message = bet.to_slice(vault);
+    verify_vrf(&bet.resolver.to_bytes(), proof, &message).ok_or(DiceError::VrfProof.into())
+}
+
+// Checks the preimage against the bet's seed of the hash chain and frees the bet's place in it
+pub fn reveal_server_seed(
+    bet: &Bet,
+    vault_state: &mut VaultState,
+    preimage: &[u8; 32],
+) -> Result<Vec<u8>> {
+    bet.require_mode(ResolutionMode::HashChain)?;
+    vault_state.reveal(bet.chain_position, preimage)?;
+    vault_state.close_chain_bet()?;
+    Ok([preimage.as_ref(), &bet.seed.to_le_bytes()].concat())
+}
+
+// Combines the house's server seed with the player's client seed and, if requested, the slot hash
+pub fn mix_entropy(bet: &Bet, slot_hashes: &AccountInfo, server_seed: Vec<u8>) -> Result<Vec<u8>> {
+    let entropy = [server_seed.as_slice(), &bet.client_seed].concat();
+    if bet.target_slot == 0 {
+        return Ok(entropy);
+    }
+    let slot_hash = slot_hash(slot_hashes, bet.target_slot)?;
+    Ok([entropy.as_slice(), &slot_hash].concat())
+}
+
+// Reads a slot's hash straight from the SlotHashes sysvar, which is too large to deserialize
+pub fn slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    find_slot_hash(&slot_hashes.try_borrow_data()?, slot)
+        .ok_or(DiceError::SlotHashUnavailable.into())
+}
+
+// Whether `slot` will never show up in SlotHashes, because it was skipped or has aged out
+pub fn slot_hash_expired(slot_hashes: &AccountInfo, slot: u64) -> Result<bool> {
+    Ok(slot_missing(&slot_hashes.try_borrow_data()?, slot))
+}
+
+// Entries are (slot, hash) pairs sorted newest first
+fn slot_hash_entries(data: &[u8]) -> Vec<&[u8]> {
+    let len = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+    data[8..].chunks_exact(40).take(len).collect()
+}
+
+fn entry_slot(entry: &[u8]) -> u64 {
+    u64::from_le_bytes(entry[..8].try_into().unwrap())
+}
+
+fn find_slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let entries = slot_hash_entries(data);
+    let index = entries
+        .binary_search_by(|entry| slot.cmp(&entry_slot(entry)))
+        .ok()?;
+
+    Some(entries[index][8..].try_into().unwrap())
+}
+
+fn slot_missing(data: &[u8], slot: u64) -> bool {
+    let newest = slot_hash_entries(data)
+        .first()
+        .map(|entry| entry_slot(entry));
+    newest.is_some_and(|newest| slot < newest) && find_slot_hash(data, slot).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SlotHashes data holding `slots`, newest first
+    fn slot_hashes_data(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for &slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn finds_slot_hashes() {
+        let data = slot_hashes_data(&[105, 104, 102, 101]);
+
+        assert_eq!(find_slot_hash(&data, 104), Some([104; 32]));
+        assert_eq!(find_slot_hash(&data, 101), Some([101; 32]));
+        assert_eq!(find_slot_hash(&data, 103), None);
+    }
+
+    #[test]
+    fn expires_skipped_and_aged_out_slots() {
+        let data = slot_hashes_data(&[105, 104, 102, 101]);
+
+        // 103 was skipped and 100 has left the window
+        assert!(slot_missing(&data, 103));
+        assert!(slot_missing(&data, 100));
 
-        Ok(())
+        // Still available, or not produced yet
+        assert!(!slot_missing(&data, 102));
+        assert!(!slot_missing(&data, 105));
+        assert!(!slot_missing(&data, 106));
+        assert!(!slot_missing(&slot_hashes_data(&[]), 106));
     }
 }
@@ -0,0 +1,242 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    ed25519::verify_ed25519_batch,
+    errors::DiceError,
+    events::BetResolved,
+    instructions::{mix_entropy, pay_resolver_reward},
+    roll::roll_from_signature,
+    state::{
+        Bet, HouseConfig, HouseStats, Leaderboard, PlayerStats, RecentResults, ResolutionMode,
+        VaultState, OUTCOMES,
+    },
+};
+
+// Resolves SOL bets in bulk. Remaining accounts are (bet, player, player stats) triples, and a
+// single Ed25519 instruction must hold a resolver signature over each bet's message digest, in
+// order.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct ResolveBets<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    pub house: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        mut,
+        seeds = [b"house_stats", vault.key().as_ref()],
+        bump = house_stats.bump
+    )]
+    pub house_stats: Account<'info, HouseStats>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard", vault.key().as_ref()],
+        bump = leaderboard.load()?.bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        mut,
+        seeds = [b"recent_results", vault.key().as_ref()],
+        bump = recent_results.load()?.bump
+    )]
+    pub recent_results: AccountLoader<'info, RecentResults>,
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+    /// CHECK: This is the slot hashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveBets<'info> {
+    pub fn resolve_bets(
+        &mut self,
+        vault_id: u16,
+        bumps: &ResolveBetsBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<Vec<BetResolved>> {
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 3 == 0,
+            DiceError::BatchAccounts
+        );
+        require!(
+            remaining_accounts.len() / 3 <= u8::MAX as usize,
+            DiceError::BatchAccounts
+        );
+
+        // A bet listed twice would be paid twice
+        let mut keys = remaining_accounts
+            .iter()
+            .step_by(3)
+            .map(|bet| bet.key())
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+        require!(
+            keys.len() == remaining_accounts.len() / 3,
+            DiceError::BatchAccounts
+        );
+
+        let bets = remaining_accounts
+            .chunks_exact(3)
+            .map(|accounts| self.load_bet(&accounts[0], &accounts[1]))
+            .collect::<Result<Vec<_>>>()?;
+
+        let messages = bets
+            .iter()
+            .map(|bet| bet.digest(&self.vault.key()))
+            .collect::<Vec<_>>();
+        let signatures = verify_ed25519_batch(
+            &self.instruction_sysvar,
+            &self.config.resolvers(Clock::get()?.slot),
+            &messages,
+        )?;
+
+        let slot = Clock::get()?.slot;
+        let mut events = Vec::with_capacity(bets.len());
+        for ((bet, sig), accounts) in bets
+            .iter()
+            .zip(signatures)
+            .zip(remaining_accounts.chunks_exact(3))
+        {
+            let entropy = mix_entropy(bet, &self.slot_hashes, sig.to_vec())?;
+            let result = roll_from_signature(&entropy, OUTCOMES as u32);
+            let won = self.settle(bet, result, &accounts[1], vault_id, bumps)?;
+            self.record_player_stats(&accounts[2], bet, won)?;
+            self.recent_results.load_mut()?.push(bet, result, slot);
+            pay_resolver_reward(&accounts[0], &self.resolver, self.config.resolver_reward)?;
+            bet.close(accounts[1].clone())?;
+            events.push(BetResolved::new(self.vault.key(), bet, result));
+        }
+
+        Ok(events)
+    }
+
+    fn load_bet(
+        &self,
+        bet: &'info AccountInfo<'info>,
+        player: &AccountInfo<'info>,
+    ) -> Result<Account<'info, Bet>> {
+        let bet_account = Account::<Bet>::try_from(bet)?;
+
+        // Same checks as the seeds and close constraints on `ResolveBet`
+        let expected = Pubkey::create_program_address(
+            &[
+                b"bet",
+                self.vault.key().as_ref(),
+                bet_account.seed.to_le_bytes().as_ref(),
+                &[bet_account.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| DiceError::BatchAccounts)?;
+        require_keys_eq!(bet.key(), expected, DiceError::BatchAccounts);
+        require_keys_eq!(
+            bet_account.mint,
+            Pubkey::default(),
+            DiceError::BatchAccounts
+        );
+        require_keys_eq!(player.key(), bet_account.player, DiceError::BatchAccounts);
+        bet_account.require_mode(ResolutionMode::Signature)?;
+        require!(
+            bet.is_writable && player.is_writable,
+            DiceError::BatchAccounts
+        );
+
+        Ok(bet_account)
+    }
+
+    fn settle(
+        &mut self,
+        bet: &Bet,
+        result: u32,
+        player: &AccountInfo<'info>,
+        vault_id: u16,
+        bumps: &ResolveBetsBumps,
+    ) -> Result<bool> {
+        self.vault_state.release(bet.locked)?;
+
+        let won = bet.bet_type.wins(result, bet.roll);
+        let payout = if won { bet.payout } else { 0 };
+        self.house_stats
+            .record_resolution(bet.amount, payout, result)?;
+        if won {
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: player.clone(),
+            };
+
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                &self.house.key().to_bytes(),
+                &vault_id.to_le_bytes(),
+                &[bumps.vault],
+            ]];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+
+            transfer(ctx, bet.payout)?;
+        }
+
+        Ok(won)
+    }
+
+    // Loaded and written back per bet, so a player with several bets in the batch never
+    // overwrites their stats with a stale copy
+    fn record_player_stats(
+        &self,
+        player_stats: &'info AccountInfo<'info>,
+        bet: &Bet,
+        won: bool,
+    ) -> Result<()> {
+        let mut stats = Account::<PlayerStats>::try_from(player_stats)?;
+        let expected = Pubkey::create_program_address(
+            &[
+                b"player_stats",
+                self.vault.key().as_ref(),
+                bet.player.as_ref(),
+                &[stats.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| DiceError::BatchAccounts)?;
+        require_keys_eq!(player_stats.key(), expected, DiceError::BatchAccounts);
+
+        if won {
+            stats.record_win(bet.payout)?;
+        } else {
+            stats.record_loss();
+        }
+
+        let payout = if won { bet.payout } else { 0 };
+        let mut leaderboard = self.leaderboard.load_mut()?;
+        stats.record_epoch(leaderboard.epoch, bet.amount, payout)?;
+        leaderboard.record(&stats);
+        stats.exit(&crate::ID)
+    }
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    ed25519::verify_ed25519_signature,
+    instructions::{mix_entropy, pay_resolver_reward, reveal_server_seed, verify_vrf_proof},
+    state::{Bet, HouseConfig, RecentResults, ResolutionMode, TokenVault, VaultState},
+    utils::transfer_tokens,
+    vrf::PROOF_LEN,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct ResolveTokenBet<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    pub house: SystemAccount<'info>,
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", vault.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        mut,
+        seeds = [b"recent_results", vault.key().as_ref()],
+        bump = recent_results.load()?.bump
+    )]
+    pub recent_results: AccountLoader<'info, RecentResults>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = player,
+        has_one = player,
+        has_one = mint,
+        seeds = [b"bet", vault.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+    /// CHECK: This is the slot hashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveTokenBet<'info> {
+    pub fn verify_ed25519_signature(&self, sig: &[u8]) -> Result<()> {
+        self.bet.require_mode(ResolutionMode::Signature)?;
+        verify_ed25519_signature(
+            &self.instruction_sysvar,
+            &self.config.resolvers(Clock::get()?.slot),
+            sig,
+            &self.bet.digest(&self.vault.key()),
+        )
+    }
+
+    pub fn verify_vrf_proof(&self, proof: &[u8; PROOF_LEN]) -> Result<[u8; 64]> {
+        verify_vrf_proof(&self.bet, &self.vault.key(), proof)
+    }
+
+    pub fn reveal_server_seed(&mut self, preimage: &[u8; 32]) -> Result<Vec<u8>> {
+        reveal_server_seed(&self.bet, &mut self.vault_state, preimage)
+    }
+
+    pub fn mix_entropy(&self, server_seed: Vec<u8>) -> Result<Vec<u8>> {
+        mix_entropy(&self.bet, &self.slot_hashes, server_seed)
+    }
+
+    pub fn resolve_bet(
+        &mut self,
+        result: u32,
+        vault_id: u16,
+        bumps: &ResolveTokenBetBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        self.token_vault.release(self.bet.locked)?;
+        pay_resolver_reward(
+            &self.bet.to_account_info(),
+            &self.resolver,
+            self.config.resolver_reward,
+        )?;
+        self.recent_results
+            .load_mut()?
+            .push(&self.bet, result, Clock::get()?.slot);
+
+        if self.bet.bet_type.wins(result, self.bet.roll) {
+            // Player wins — the reserve was grossed up so the payout lands net of transfer fees
+            let amount = self.bet.locked;
+
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                &self.house.key().to_bytes(),
+                &vault_id.to_le_bytes(),
+                &[bumps.vault],
+            ]];
+
+            transfer_tokens(
+                &self.token_program,
+                &self.vault_ata,
+                &self.mint,
+                &self.player_ata,
+                self.vault.to_account_info(),
+                remaining_accounts,
+                amount,
+                signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
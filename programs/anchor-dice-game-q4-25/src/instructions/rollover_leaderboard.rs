@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::DiceError,
+    state::{HouseConfig, Leaderboard},
+};
+
+// Anyone may roll the leaderboard over once its epoch has run, paying for the archive
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16, epoch: u64)]
+pub struct RolloverLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub house: SystemAccount<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard", vault.key().as_ref()],
+        bump = leaderboard.load()?.bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        init,
+        payer = payer,
+        space = Leaderboard::DISCRIMINATOR.len() + std::mem::size_of::<Leaderboard>(),
+        seeds = [b"leaderboard", vault.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub archive: AccountLoader<'info, Leaderboard>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RolloverLeaderboard<'info> {
+    pub fn rollover_leaderboard(
+        &mut self,
+        epoch: u64,
+        bumps: &RolloverLeaderboardBumps,
+    ) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let mut leaderboard = self.leaderboard.load_mut()?;
+        require!(leaderboard.epoch == epoch, DiceError::LeaderboardEpoch);
+
+        let epoch_end = leaderboard
+            .start_slot
+            .checked_add(self.config.leaderboard_epoch)
+            .ok_or(DiceError::Overflow)?;
+        require!(slot >= epoch_end, DiceError::LeaderboardEpochRunning);
+
+        let mut archive = self.archive.load_init()?;
+        *archive = *leaderboard;
+        archive.end_slot = slot;
+        archive.bump = bumps.archive;
+
+        leaderboard.reset(slot);
+        Ok(())
+    }
+}
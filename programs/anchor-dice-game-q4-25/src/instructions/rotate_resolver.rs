@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::HouseConfig;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct RotateResolver<'info> {
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config", vault.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HouseConfig>,
+}
+
+impl<'info> RotateResolver<'info> {
+    // The outgoing key keeps signing for `grace_slots` so in-flight signature bets aren't stranded
+    pub fn rotate_resolver(&mut self, resolver: Pubkey, grace_slots: u64) -> Result<()> {
+        self.config.rotate_resolver(resolver, grace_slots)
+    }
+}
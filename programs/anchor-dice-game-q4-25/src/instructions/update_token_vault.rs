@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::TokenVault;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct UpdateTokenVault<'info> {
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", vault.key().as_ref(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+}
+
+impl<'info> UpdateTokenVault<'info> {
+    pub fn update_token_vault(&mut self, min_bet: u64, max_bet: u64) -> Result<()> {
+        self.token_vault.update(min_bet, max_bet)
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{errors::DiceError, state::VaultState};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub house: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"vault_state", vault.key().as_ref()],
+        bump = vault_state.bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    pub fn withdraw(&mut self, amount: u64, vault_id: u16, bumps: &WithdrawBumps) -> Result<()> {
+        // Keep enough to pay every pending bet and stay rent-exempt
+        let free_balance = self.vault_state.free_balance(self.vault.lamports())?;
+        require!(amount <= free_balance, DiceError::InsufficientFunds);
+
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.house.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            &self.house.key().to_bytes(),
+            &vault_id.to_le_bytes(),
+            &[bumps.vault],
+        ]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        transfer(ctx, amount)
+    }
+}
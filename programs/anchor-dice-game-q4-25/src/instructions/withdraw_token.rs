@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{errors::DiceError, state::TokenVault, utils::transfer_tokens};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: u16)]
+pub struct WithdrawToken<'info> {
+    pub house: Signer<'info>,
+    #[account(
+        seeds = [b"vault", house.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [b"token_vault", vault.key().as_ref(), mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = house,
+        associated_token::token_program = token_program
+    )]
+    pub house_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawToken<'info> {
+    pub fn withdraw_token(
+        &mut self,
+        amount: u64,
+        vault_id: u16,
+        bumps: &WithdrawTokenBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Keep enough to pay every pending bet
+        let free_balance = self.token_vault.free_balance(self.vault_ata.amount);
+        require!(amount <= free_balance, DiceError::InsufficientFunds);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            &self.house.key().to_bytes(),
+            &vault_id.to_le_bytes(),
+            &[bumps.vault],
+        ]];
+
+        transfer_tokens(
+            &self.token_program,
+            &self.vault_ata,
+            &self.mint,
+            &self.house_ata,
+            self.vault.to_account_info(),
+            remaining_accounts,
+            amount,
+            signer_seeds,
+        )
+    }
+}
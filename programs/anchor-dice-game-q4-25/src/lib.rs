@@ -1,33 +1,374 @@
+pub mod ed25519;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod roll;
 pub mod state;
+pub mod utils;
+pub mod vrf;
 
 use anchor_lang::prelude::*;
 
+pub use events::*;
 pub use instructions::*;
+pub use roll::*;
 pub use state::*;
 
 declare_id!("DZDRzKdTu4SweFFjDutMgPqu55Qt9TLbhWG1cMAikYVp");
 
+// Handlers allowing `unused_variables` take `vault_id` only for the account seeds
 #[program]
 pub mod anchor_dice_game_q4_25 {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, amount: u64) -> Result<()> {
-        ctx.accounts.init(amount)
+    #[allow(unused_variables)]
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        vault_id: u16,
+        amount: u64,
+        params: ConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.init_config(&ctx.bumps, &params)?;
+        ctx.accounts.init(amount)?;
+
+        let vault = ctx.accounts.vault.key();
+        emit_cpi!(ConfigUpdated { vault, params });
+        emit_cpi!(VaultFunded {
+            vault,
+            mint: Pubkey::default(),
+            amount,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        vault_id: u16,
+        params: ConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.update_config(&params)?;
+        emit_cpi!(ConfigUpdated {
+            vault: ctx.accounts.vault.key(),
+            params,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn rotate_resolver(
+        ctx: Context<RotateResolver>,
+        vault_id: u16,
+        resolver: Pubkey,
+        grace_slots: u64,
+    ) -> Result<()> {
+        ctx.accounts.rotate_resolver(resolver, grace_slots)?;
+
+        let config = &ctx.accounts.config;
+        emit_cpi!(ResolverRotated {
+            vault: ctx.accounts.vault.key(),
+            resolver: config.resolver,
+            previous_resolver: config.previous_resolver,
+            previous_resolver_expiry: config.previous_resolver_expiry,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn rollover_leaderboard(
+        ctx: Context<RolloverLeaderboard>,
+        vault_id: u16,
+        epoch: u64,
+    ) -> Result<()> {
+        ctx.accounts.rollover_leaderboard(epoch, &ctx.bumps)?;
+
+        let leaderboard = ctx.accounts.leaderboard.load()?;
+        emit_cpi!(LeaderboardRolledOver {
+            vault: ctx.accounts.vault.key(),
+            epoch,
+            slot: leaderboard.start_slot,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn deposit(ctx: Context<Deposit>, vault_id: u16, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)?;
+        emit_cpi!(VaultFunded {
+            vault: ctx.accounts.vault.key(),
+            mint: Pubkey::default(),
+            amount,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, vault_id: u16, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount, vault_id, &ctx.bumps)?;
+        emit_cpi!(VaultWithdrawn {
+            vault: ctx.accounts.vault.key(),
+            mint: Pubkey::default(),
+            amount,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn commit_hash_chain(
+        ctx: Context<CommitHashChain>,
+        vault_id: u16,
+        tip: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.commit_hash_chain(tip)?;
+        emit_cpi!(HashChainCommitted {
+            vault: ctx.accounts.vault.key(),
+            tip,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        vault_id: u16,
+        seed: u128,
+        roll: u16,
+        bet_type: BetType,
+        amount: u64,
+        slot_hash: bool,
+        client_seed: [u8; 32],
+        mode: ResolutionMode,
+    ) -> Result<()> {
+        ctx.accounts.create_bet(
+            &ctx.bumps,
+            seed,
+            roll,
+            bet_type,
+            amount,
+            slot_hash,
+            client_seed,
+            mode,
+        )?;
+        ctx.accounts.deposit(amount)?;
+        emit_cpi!(BetPlaced::new(ctx.accounts.vault.key(), &ctx.accounts.bet));
+        Ok(())
+    }
+
+    pub fn resolve_bet(ctx: Context<ResolveBet>, vault_id: u16, sig: Vec<u8>) -> Result<()> {
+        ctx.accounts.verify_ed25519_signature(&sig)?;
+        let entropy = ctx.accounts.mix_entropy(sig)?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts.resolve_bet(result, vault_id, &ctx.bumps)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
     }
 
-    pub fn place_bet(ctx: Context<PlaceBet>, seed: u128, roll: u8, amount: u64) -> Result<()> {
-        ctx.accounts.create_bet(&ctx.bumps, seed, roll, amount)?;
-        ctx.accounts.deposit(amount)
+    pub fn resolve_bet_vrf(ctx: Context<ResolveBet>, vault_id: u16, proof: [u8; 80]) -> Result<()> {
+        let output = ctx.accounts.verify_vrf_proof(&proof)?;
+        let entropy = ctx.accounts.mix_entropy(output.to_vec())?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts.resolve_bet(result, vault_id, &ctx.bumps)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
     }
 
-    pub fn resolve_bet(ctx: Context<ResolveBet>, sig: Vec<u8>) -> Result<()> {
+    pub fn resolve_bet_hash_chain(
+        ctx: Context<ResolveBet>,
+        vault_id: u16,
+        preimage: [u8; 32],
+    ) -> Result<()> {
+        let seed = ctx.accounts.reveal_server_seed(&preimage)?;
+        let entropy = ctx.accounts.mix_entropy(seed)?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts.resolve_bet(result, vault_id, &ctx.bumps)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
+    }
+
+    pub fn resolve_bets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveBets<'info>>,
+        vault_id: u16,
+    ) -> Result<()> {
+        let events = ctx
+            .accounts
+            .resolve_bets(vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        for event in events {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    pub fn refund_bet(ctx: Context<RefundBet>, vault_id: u16) -> Result<()> {
+        ctx.accounts.refund_bet(vault_id, &ctx.bumps)?;
+        emit_cpi!(BetRefunded::new(ctx.accounts.vault.key(), &ctx.accounts.bet));
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn init_token_vault(
+        ctx: Context<InitTokenVault>,
+        vault_id: u16,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        ctx.accounts.init_token_vault(&ctx.bumps, min_bet, max_bet)?;
+        emit_cpi!(TokenVaultUpdated {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.mint.key(),
+            min_bet,
+            max_bet,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn update_token_vault(
+        ctx: Context<UpdateTokenVault>,
+        vault_id: u16,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        ctx.accounts.update_token_vault(min_bet, max_bet)?;
+        emit_cpi!(TokenVaultUpdated {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.token_vault.mint,
+            min_bet,
+            max_bet,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    pub fn deposit_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositToken<'info>>,
+        vault_id: u16,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.deposit_token(amount, ctx.remaining_accounts)?;
+        emit_cpi!(VaultFunded {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawToken<'info>>,
+        vault_id: u16,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .withdraw_token(amount, vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        emit_cpi!(VaultWithdrawn {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_token_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceTokenBet<'info>>,
+        vault_id: u16,
+        seed: u128,
+        roll: u16,
+        bet_type: BetType,
+        amount: u64,
+        slot_hash: bool,
+        client_seed: [u8; 32],
+        mode: ResolutionMode,
+    ) -> Result<()> {
+        ctx.accounts.create_bet(
+            &ctx.bumps,
+            seed,
+            roll,
+            bet_type,
+            amount,
+            slot_hash,
+            client_seed,
+            mode,
+        )?;
+        ctx.accounts.deposit(amount, ctx.remaining_accounts)?;
+        emit_cpi!(BetPlaced::new(ctx.accounts.vault.key(), &ctx.accounts.bet));
+        Ok(())
+    }
+
+    pub fn resolve_token_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveTokenBet<'info>>,
+        vault_id: u16,
+        sig: Vec<u8>,
+    ) -> Result<()> {
         ctx.accounts.verify_ed25519_signature(&sig)?;
-        ctx.accounts.resolve_bet(&sig, &ctx.bumps)
+        let entropy = ctx.accounts.mix_entropy(sig)?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts
+            .resolve_bet(result, vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
+    }
+
+    pub fn resolve_token_bet_vrf<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveTokenBet<'info>>,
+        vault_id: u16,
+        proof: [u8; 80],
+    ) -> Result<()> {
+        let output = ctx.accounts.verify_vrf_proof(&proof)?;
+        let entropy = ctx.accounts.mix_entropy(output.to_vec())?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts
+            .resolve_bet(result, vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
+    }
+
+    pub fn resolve_token_bet_hash_chain<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveTokenBet<'info>>,
+        vault_id: u16,
+        preimage: [u8; 32],
+    ) -> Result<()> {
+        let seed = ctx.accounts.reveal_server_seed(&preimage)?;
+        let entropy = ctx.accounts.mix_entropy(seed)?;
+        let result = roll_from_signature(&entropy, OUTCOMES as u32);
+        ctx.accounts
+            .resolve_bet(result, vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        emit_cpi!(BetResolved::new(
+            ctx.accounts.vault.key(),
+            &ctx.accounts.bet,
+            result
+        ));
+        Ok(())
     }
 
-    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
-        ctx.accounts.refund_bet(&ctx.bumps)
+    pub fn refund_token_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundTokenBet<'info>>,
+        vault_id: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .refund_bet(vault_id, &ctx.bumps, ctx.remaining_accounts)?;
+        emit_cpi!(BetRefunded::new(ctx.accounts.vault.key(), &ctx.accounts.bet));
+        Ok(())
     }
 }
@@ -0,0 +1,79 @@
+use solana_program::hash::hash;
+
+/// Maps a house signature to an outcome in `1..=outcome_space`.
+///
+/// SHA-256 of the signature is read as little-endian u64 words, and any word in the
+/// biased tail above the largest multiple of `outcome_space` is skipped (rehashing if
+/// all four are), so every outcome is equally likely. `outcome_space` must be non-zero.
+pub fn roll_from_signature(sig: &[u8], outcome_space: u32) -> u32 {
+    let n = outcome_space as u64;
+    // 2^64 mod n: the size of the biased tail
+    let tail = (u64::MAX % n + 1) % n;
+
+    let mut digest = hash(sig).to_bytes();
+    loop {
+        for word in digest.chunks_exact(8) {
+            let value = u64::from_le_bytes(word.try_into().unwrap());
+            if value <= u64::MAX - tail {
+                return (value % n) as u32 + 1;
+            }
+        }
+        digest = hash(&digest).to_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chi_square(outcome_space: u32, samples: u32) -> f64 {
+        let mut counts = vec![0u32; outcome_space as usize];
+        for i in 0..samples {
+            let roll = roll_from_signature(&i.to_le_bytes(), outcome_space);
+            counts[roll as usize - 1] += 1;
+        }
+
+        let expected = samples as f64 / outcome_space as f64;
+        counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn rolls_stay_in_range() {
+        for outcome_space in [1, 2, 6, 100, 10_000, u32::MAX] {
+            for i in 0u32..1_000 {
+                let roll = roll_from_signature(&i.to_le_bytes(), outcome_space);
+                assert!((1..=outcome_space).contains(&roll));
+            }
+        }
+    }
+
+    #[test]
+    fn rolls_are_deterministic() {
+        let sig = [7u8; 64];
+        assert_eq!(
+            roll_from_signature(&sig, 10_000),
+            roll_from_signature(&sig, 10_000)
+        );
+    }
+
+    #[test]
+    fn distribution_is_uniform_over_100() {
+        // 99 degrees of freedom: the 99.9th percentile is ~148.2
+        assert!(chi_square(100, 200_000) < 148.2);
+    }
+
+    #[test]
+    fn distribution_is_uniform_over_6() {
+        // 5 degrees of freedom: the 99.9th percentile is ~20.5
+        assert!(chi_square(6, 60_000) < 20.5);
+    }
+
+    #[test]
+    fn distribution_is_uniform_over_outcomes() {
+        // 9999 degrees of freedom: the 99.9th percentile is ~10_535
+        assert!(chi_square(10_000, 1_000_000) < 10_535.0);
+    }
+}
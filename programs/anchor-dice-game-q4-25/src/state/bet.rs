@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use solana_program::hash::hash;
+
+use crate::errors::DiceError;
+
+// Rolls land on 1..=OUTCOMES, giving 0.01% resolution
+pub const OUTCOMES: u16 = 10_000;
+
+// Bumped whenever the layout of `BetMessage` changes
+pub const MESSAGE_VERSION: u8 = 2;
+
+// Slots between placement and the slot whose hash is mixed into the roll
+pub const SLOT_HASH_DELAY: u64 = 2;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bet {
+    pub player: Pubkey,
+    // Default pubkey for SOL bets
+    pub mint: Pubkey,
+    pub seed: u128,
+    // Player's contribution to the roll entropy
+    pub client_seed: [u8; 32],
+    pub slot: u64,
+    // Slot whose hash is mixed into the roll, 0 when unused
+    pub target_slot: u64,
+    pub amount: u64,
+    // What the player nets on a win
+    pub payout: u64,
+    // Reserved in the vault for the payout, grossed up by the mint's transfer fee
+    pub locked: u64,
+    // Threshold for over/under, low end for ranges, the number itself for exact bets
+    pub roll: u16,
+    pub bet_type: BetType,
+    pub mode: ResolutionMode,
+    // Seed of the hash chain that resolves the bet, 0 for other modes
+    pub chain_position: u64,
+    // Resolver key at placement, the only key whose VRF proof resolves the bet
+    pub resolver: Pubkey,
+    pub bump : u8
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BetType {
+    Over,
+    Under,
+    // Wins when the result lands in `roll..=high`
+    Range { high: u16 },
+    Exact,
+}
+
+// How the bet must be resolved, chosen at placement so the house can't pick per bet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ResolutionMode {
+    // Ed25519 signature, which the house could grind with non-deterministic nonces
+    Signature,
+    // ECVRF proof, unique per key and message
+    Vrf,
+    // Next preimage of the committed hash chain
+    HashChain,
+}
+
+impl BetType {
+    pub fn wins(&self, result: u32, roll: u16) -> bool {
+        match self {
+            BetType::Over => result > roll as u32,
+            BetType::Under => result < roll as u32,
+            BetType::Range { high } => (roll as u32..=*high as u32).contains(&result),
+            BetType::Exact => result == roll as u32,
+        }
+    }
+
+    // Number of the OUTCOMES that win against `roll`
+    pub fn win_chance(&self, roll: u16) -> u16 {
+        match self {
+            BetType::Over => OUTCOMES - roll,
+            BetType::Under => roll - 1,
+            BetType::Range { high } => high - roll + 1,
+            BetType::Exact => 1,
+        }
+    }
+}
+
+impl Bet {
+    
+    pub fn message(&self, vault: &Pubkey) -> BetMessage {
+        BetMessage {
+            version: MESSAGE_VERSION,
+            program_id: crate::ID,
+            vault: *vault,
+            player: self.player,
+            mint: self.mint,
+            seed: self.seed,
+            client_seed: self.client_seed,
+            slot: self.slot,
+            target_slot: self.target_slot,
+            amount: self.amount,
+            roll: self.roll,
+            bet_type: self.bet_type,
+            mode: self.mode,
+            resolver: self.resolver,
+            bump: self.bump,
+        }
+    }
+
+    pub fn require_mode(&self, mode: ResolutionMode) -> Result<()> {
+        require!(self.mode == mode, DiceError::ResolutionModeMismatch);
+        Ok(())
+    }
+
+    // Message the house proves over to resolve this bet by VRF
+    pub fn to_slice(&self, vault: &Pubkey) -> Vec<u8> {
+        self.message(vault).to_bytes()
+    }
+
+    // Hash the house signs to resolve this bet by Ed25519 signature
+    pub fn digest(&self, vault: &Pubkey) -> [u8; 32] {
+        self.message(vault).digest()
+    }
+}
+
+// Borsh layout of the resolved message. The version, program id and vault keep a signature
+// from being replayed against another deployment or message format.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct BetMessage {
+    pub version: u8,
+    pub program_id: Pubkey,
+    pub vault: Pubkey,
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub seed: u128,
+    pub client_seed: [u8; 32],
+    pub slot: u64,
+    pub target_slot: u64,
+    pub amount: u64,
+    pub roll: u16,
+    pub bet_type: BetType,
+    pub mode: ResolutionMode,
+    pub resolver: Pubkey,
+    pub bump: u8,
+}
+
+impl BetMessage {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut s = Vec::new();
+        self.serialize(&mut s).unwrap();
+        s
+    }
+
+    // SHA-256 of the message, so each signature in an Ed25519 instruction carries 32 bytes
+    // of message however the layout grows
+    pub fn digest(&self) -> [u8; 32] {
+        hash(&self.to_bytes()).to_bytes()
+    }
+
+    // Parses a signed message, rejecting other versions and other programs' messages
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let message = Self::try_from_slice(data).map_err(|_| DiceError::Ed25519Message)?;
+        require!(
+            message.version == MESSAGE_VERSION,
+            DiceError::MessageVersion
+        );
+        require_keys_eq!(message.program_id, crate::ID, DiceError::Ed25519Message);
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> BetMessage {
+        BetMessage {
+            version: MESSAGE_VERSION,
+            program_id: crate::ID,
+            vault: Pubkey::new_unique(),
+            player: Pubkey::new_unique(),
+            mint: Pubkey::default(),
+            seed: 42,
+            client_seed: [7; 32],
+            slot: 100,
+            target_slot: 102,
+            amount: 10_000_000,
+            roll: 4000,
+            bet_type: BetType::Range { high: 6000 },
+            mode: ResolutionMode::Signature,
+            resolver: Pubkey::new_unique(),
+            bump: 254,
+        }
+    }
+
+    #[test]
+    fn decodes_encoded_message() {
+        let message = message();
+        assert!(BetMessage::decode(&message.to_bytes()).unwrap() == message);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let message = BetMessage {
+            version: MESSAGE_VERSION - 1,
+            ..message()
+        };
+        assert_eq!(
+            BetMessage::decode(&message.to_bytes()).err(),
+            Some(DiceError::MessageVersion.into())
+        );
+    }
+
+    #[test]
+    fn rejects_other_programs() {
+        let message = BetMessage {
+            program_id: Pubkey::new_unique(),
+            ..message()
+        };
+        assert_eq!(
+            BetMessage::decode(&message.to_bytes()).err(),
+            Some(DiceError::Ed25519Message.into())
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let bytes = message().to_bytes();
+        assert_eq!(
+            BetMessage::decode(&bytes[..bytes.len() - 1]).err(),
+            Some(DiceError::Ed25519Message.into())
+        );
+    }
+}
\ No newline at end of file
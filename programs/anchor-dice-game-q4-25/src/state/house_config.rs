@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::DiceError,
+    state::{BetType, OUTCOMES},
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct HouseConfig {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub min_roll: u16,
+    pub max_roll: u16,
+    pub refund_timeout: u64,
+    // House edge in basis points
+    pub house_edge: u16,
+    // Largest payout a single bet may claim, in basis points of the vault balance
+    pub max_payout_ratio: u16,
+    // Lamports taken from the bet rent to reward whoever submits the resolution
+    pub resolver_reward: u64,
+    // Key that signs resolutions, kept apart from the house key that owns the vault
+    pub resolver: Pubkey,
+    // Key being rotated out, its signatures still accepted until `previous_resolver_expiry`
+    pub previous_resolver: Pubkey,
+    pub previous_resolver_expiry: u64,
+    // Slots a leaderboard epoch runs before it may be rolled over
+    pub leaderboard_epoch: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub min_roll: u16,
+    pub max_roll: u16,
+    pub refund_timeout: u64,
+    pub house_edge: u16,
+    pub max_payout_ratio: u16,
+    pub resolver_reward: u64,
+    pub leaderboard_epoch: u64,
+}
+
+impl HouseConfig {
+    pub fn update(&mut self, params: &ConfigParams) -> Result<()> {
+        // Bounds are for roll-over bets, so 1..OUTCOMES keeps the win chance non-zero
+        require!(params.min_bet <= params.max_bet, DiceError::InvalidConfig);
+        require!(params.min_roll >= 1, DiceError::InvalidConfig);
+        require!(params.max_roll < OUTCOMES, DiceError::InvalidConfig);
+        require!(params.min_roll <= params.max_roll, DiceError::InvalidConfig);
+        require!(params.house_edge < 10_000, DiceError::InvalidConfig);
+        // The safest bet wins OUTCOMES - min_roll times out of OUTCOMES. Capping that at
+        // 10_000 - house_edge keeps every payout at or above the stake, so the payout reserved
+        // at placement also covers refunding the stake.
+        require!(
+            OUTCOMES - params.min_roll <= 10_000 - params.house_edge,
+            DiceError::InvalidConfig
+        );
+        require!(params.max_payout_ratio <= 10_000, DiceError::InvalidConfig);
+
+        self.min_bet = params.min_bet;
+        self.max_bet = params.max_bet;
+        self.min_roll = params.min_roll;
+        self.max_roll = params.max_roll;
+        self.refund_timeout = params.refund_timeout;
+        self.house_edge = params.house_edge;
+        self.max_payout_ratio = params.max_payout_ratio;
+        self.resolver_reward = params.resolver_reward;
+        self.leaderboard_epoch = params.leaderboard_epoch;
+        Ok(())
+    }
+
+    // Keys whose signatures resolve bets at `slot`. VRF bets only take the key they were placed
+    // under, since a proof from either key would let the house choose between two outcomes.
+    pub fn resolvers(&self, slot: u64) -> Vec<Pubkey> {
+        let mut resolvers = vec![self.resolver];
+        if slot < self.previous_resolver_expiry {
+            resolvers.push(self.previous_resolver);
+        }
+        resolvers
+    }
+
+    // Only one outgoing key is kept, so rotating again drops the previous one at once. That is
+    // also how a compromised key is revoked without waiting out its grace period.
+    pub fn rotate_resolver(&mut self, resolver: Pubkey, grace_slots: u64) -> Result<()> {
+        self.previous_resolver = self.resolver;
+        self.previous_resolver_expiry = Clock::get()?
+            .slot
+            .checked_add(grace_slots)
+            .ok_or(DiceError::Overflow)?;
+        self.resolver = resolver;
+        Ok(())
+    }
+
+    pub fn check_roll(&self, roll: u16, bet_type: BetType) -> Result<()> {
+        match bet_type {
+            BetType::Over => {
+                require!(roll >= self.min_roll, DiceError::MinimumRoll);
+                require!(roll <= self.max_roll, DiceError::MaximumRoll);
+            }
+            // Roll-under bounds mirror the roll-over ones so both sides share the same win chances
+            BetType::Under => {
+                require!(roll >= OUTCOMES + 1 - self.max_roll, DiceError::MinimumRoll);
+                require!(roll <= OUTCOMES + 1 - self.min_roll, DiceError::MaximumRoll);
+            }
+            // A range can't win more often than the safest roll-over bet
+            BetType::Range { high } => {
+                require!(roll >= 1, DiceError::MinimumRoll);
+                require!(high <= OUTCOMES, DiceError::MaximumRoll);
+                require!(roll <= high, DiceError::InvalidRange);
+                require!(
+                    bet_type.win_chance(roll) <= OUTCOMES - self.min_roll,
+                    DiceError::InvalidRange
+                );
+            }
+            BetType::Exact => {
+                require!(roll >= 1, DiceError::MinimumRoll);
+                require!(roll <= OUTCOMES, DiceError::MaximumRoll);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn payout(&self, amount: u64, roll: u16, bet_type: BetType) -> Result<u64> {
+        // Fair multiplier is OUTCOMES / win chance, scaled down by the house edge (also in bps)
+        let payout = (amount as u128)
+            .checked_mul(10_000 - self.house_edge as u128)
+            .ok_or(DiceError::Overflow)?
+            .checked_div(bet_type.win_chance(roll) as u128)
+            .ok_or(DiceError::Overflow)?;
+
+        u64::try_from(payout).map_err(|_| DiceError::Overflow.into())
+    }
+
+    pub fn max_payout(&self, bankroll: u64) -> Result<u64> {
+        let max_payout = (bankroll as u128)
+            .checked_mul(self.max_payout_ratio as u128)
+            .ok_or(DiceError::Overflow)?
+            .checked_div(10_000)
+            .ok_or(DiceError::Overflow)?;
+
+        Ok(max_payout as u64)
+    }
+}
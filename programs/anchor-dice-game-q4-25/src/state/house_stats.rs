@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::DiceError, state::OUTCOMES};
+
+pub const HISTOGRAM_BUCKETS: usize = 100;
+
+// Cumulative SOL results for one vault, in lamports
+#[account]
+#[derive(InitSpace)]
+pub struct HouseStats {
+    // Amount wagered on resolved bets
+    pub volume: u64,
+    pub payouts: u64,
+    pub refunds: u64,
+    // Wagers kept minus payouts made
+    pub pnl: i64,
+    pub bet_count: u64,
+    pub refund_count: u64,
+    // Resolved rolls per 1% bucket, so 1..=100 land in bucket 0 and 9901..=10000 in bucket 99
+    pub roll_histogram: [u64; HISTOGRAM_BUCKETS],
+    pub bump: u8,
+}
+
+impl HouseStats {
+    pub fn record_resolution(&mut self, amount: u64, payout: u64, roll: u32) -> Result<()> {
+        self.volume = self.volume.checked_add(amount).ok_or(DiceError::Overflow)?;
+        self.payouts = self
+            .payouts
+            .checked_add(payout)
+            .ok_or(DiceError::Overflow)?;
+        self.pnl = self
+            .pnl
+            .checked_add(amount as i64)
+            .and_then(|pnl| pnl.checked_sub(payout as i64))
+            .ok_or(DiceError::Overflow)?;
+        self.bet_count += 1;
+
+        let bucket = (roll as usize - 1) * HISTOGRAM_BUCKETS / OUTCOMES as usize;
+        self.roll_histogram[bucket] += 1;
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.refunds = self
+            .refunds
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+        self.refund_count += 1;
+        Ok(())
+    }
+}
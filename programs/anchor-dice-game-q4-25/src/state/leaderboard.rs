@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PlayerStats;
+
+pub const LEADERBOARD_SIZE: usize = 10;
+
+#[zero_copy]
+#[derive(Default)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub volume: u64,
+    pub net_winnings: i64,
+}
+
+// Top players at one vault over a leaderboard epoch, in lamports. Archived epochs keep the
+// same layout under their own address.
+#[account(zero_copy)]
+pub struct Leaderboard {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub start_slot: u64,
+    // Zero until the epoch is rolled over
+    pub end_slot: u64,
+    // Best first, with empty slots at the end
+    pub by_net_winnings: [LeaderboardEntry; LEADERBOARD_SIZE],
+    pub by_volume: [LeaderboardEntry; LEADERBOARD_SIZE],
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl Leaderboard {
+    // `stats` must already hold the player's totals for this epoch
+    pub fn record(&mut self, stats: &PlayerStats) {
+        let entry = LeaderboardEntry {
+            player: stats.player,
+            volume: stats.epoch_volume,
+            net_winnings: stats.epoch_net_winnings,
+        };
+        rank(&mut self.by_net_winnings, entry, |entry| {
+            entry.net_winnings as i128
+        });
+        rank(&mut self.by_volume, entry, |entry| entry.volume as i128);
+    }
+
+    pub fn reset(&mut self, slot: u64) {
+        self.epoch += 1;
+        self.start_slot = slot;
+        self.end_slot = 0;
+        self.by_net_winnings = [LeaderboardEntry::default(); LEADERBOARD_SIZE];
+        self.by_volume = [LeaderboardEntry::default(); LEADERBOARD_SIZE];
+    }
+}
+
+// Moves the player's entry to its place by `score`, or drops it if it no longer makes the cut
+fn rank(
+    entries: &mut [LeaderboardEntry],
+    entry: LeaderboardEntry,
+    score: impl Fn(&LeaderboardEntry) -> i128,
+) {
+    if let Some(index) = entries.iter().position(|e| e.player == entry.player) {
+        entries[index..].rotate_left(1);
+        entries[entries.len() - 1] = LeaderboardEntry::default();
+    }
+
+    let Some(index) = entries
+        .iter()
+        .position(|e| e.player == Pubkey::default() || score(&entry) > score(e))
+    else {
+        return;
+    };
+    entries[index..].rotate_right(1);
+    entries[index] = entry;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(player: u8, net_winnings: i64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            player: Pubkey::new_from_array([player; 32]),
+            volume: 0,
+            net_winnings,
+        }
+    }
+
+    #[test]
+    fn keeps_players_ranked_once() {
+        let mut entries = [LeaderboardEntry::default(); 3];
+        let score = |entry: &LeaderboardEntry| entry.net_winnings as i128;
+
+        rank(&mut entries, entry(1, -5), score);
+        rank(&mut entries, entry(2, 10), score);
+        rank(&mut entries, entry(3, 3), score);
+        assert_eq!(entries.map(|e| e.net_winnings), [10, 3, -5]);
+
+        // A better result moves the player up instead of adding a second entry
+        rank(&mut entries, entry(1, 20), score);
+        assert_eq!(entries.map(|e| e.net_winnings), [20, 10, 3]);
+
+        // Slipping below a newcomer drops the player
+        rank(&mut entries, entry(3, -1), score);
+        rank(&mut entries, entry(4, 1), score);
+        assert_eq!(entries.map(|e| e.net_winnings), [20, 10, 1]);
+        assert!(!entries.iter().any(|e| e.player == entry(3, 0).player));
+    }
+}
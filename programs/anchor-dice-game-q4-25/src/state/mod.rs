@@ -1,24 +1,23 @@
-use anchor_lang::prelude::*;
+pub mod bet;
+pub use bet::*;
 
-#[account]
-#[derive(InitSpace)]
-pub struct Bet {
-    pub player: Pubkey,
-    pub seed: u128,
-    pub slot: u64,
-    pub amount: u64,
-    pub roll: u8,
-    pub bump : u8
-}
+pub mod house_config;
+pub use house_config::*;
 
-impl Bet {
-    
-    pub fn to_slice(&self) -> Vec<u8> {
-        let mut s = self.player.to_bytes().to_vec();
-        s.extend_from_slice(&self.seed.to_le_bytes());
-        s.extend_from_slice(&self.slot.to_le_bytes());
-        s.extend_from_slice(&self.amount.to_le_bytes());
-        s.extend_from_slice(&[self.roll, self.bump]);
-        s        
-    }
-}
\ No newline at end of file
+pub mod vault_state;
+pub use vault_state::*;
+
+pub mod token_vault;
+pub use token_vault::*;
+
+pub mod player_stats;
+pub use player_stats::*;
+
+pub mod house_stats;
+pub use house_stats::*;
+
+pub mod leaderboard;
+pub use leaderboard::*;
+
+pub mod recent_results;
+pub use recent_results::*;
\ No newline at end of file
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DiceError;
+
+// A player's SOL betting history at one vault, in lamports
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    pub vault: Pubkey,
+    pub total_wagered: u64,
+    pub total_paid_out: u64,
+    pub bets_won: u64,
+    pub bets_lost: u64,
+    pub bets_refunded: u64,
+    pub biggest_win: u64,
+    // Positive for consecutive wins, negative for consecutive losses
+    pub current_streak: i64,
+    // Totals for the leaderboard epoch in `epoch`, reset when a new one begins
+    pub epoch: u64,
+    pub epoch_volume: u64,
+    pub epoch_net_winnings: i64,
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    pub fn record_bet(&mut self, amount: u64) -> Result<()> {
+        self.total_wagered = self
+            .total_wagered
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_win(&mut self, payout: u64) -> Result<()> {
+        self.total_paid_out = self
+            .total_paid_out
+            .checked_add(payout)
+            .ok_or(DiceError::Overflow)?;
+        self.bets_won += 1;
+        self.biggest_win = self.biggest_win.max(payout);
+        self.current_streak = self.current_streak.max(0) + 1;
+        Ok(())
+    }
+
+    pub fn record_loss(&mut self) {
+        self.bets_lost += 1;
+        self.current_streak = self.current_streak.min(0) - 1;
+    }
+
+    pub fn record_epoch(&mut self, epoch: u64, amount: u64, payout: u64) -> Result<()> {
+        if self.epoch != epoch {
+            self.epoch = epoch;
+            self.epoch_volume = 0;
+            self.epoch_net_winnings = 0;
+        }
+        self.epoch_volume = self
+            .epoch_volume
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+        self.epoch_net_winnings = self
+            .epoch_net_winnings
+            .checked_add(payout as i64)
+            .and_then(|net| net.checked_sub(amount as i64))
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    // Refunded bets are neither wins nor losses, so the streak carries on
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.total_wagered = self
+            .total_wagered
+            .checked_sub(amount)
+            .ok_or(DiceError::Overflow)?;
+        self.bets_refunded += 1;
+        Ok(())
+    }
+}
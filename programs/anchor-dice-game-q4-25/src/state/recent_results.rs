@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Bet, BetType};
+
+pub const RECENT_RESULTS_SIZE: usize = 32;
+
+#[zero_copy]
+#[derive(Default)]
+pub struct RecentResult {
+    pub player: Pubkey,
+    // Default pubkey for SOL bets
+    pub mint: Pubkey,
+    // Little-endian, since a `u128` would change the alignment between targets
+    pub seed: [u8; 16],
+    pub amount: u64,
+    pub payout: u64,
+    // Slot the bet was resolved at
+    pub slot: u64,
+    pub target: u16,
+    // Upper bound of a range bet, 0 for other bet types
+    pub high: u16,
+    pub roll: u16,
+    // 0 over, 1 under, 2 range, 3 exact, matching the `BetType` variant order
+    pub bet_type: u8,
+    pub won: u8,
+}
+
+// The last `RECENT_RESULTS_SIZE` resolutions at one vault, SOL and token bets alike, so settled
+// bets stay visible after their accounts close
+#[account(zero_copy)]
+pub struct RecentResults {
+    pub vault: Pubkey,
+    // Resolutions recorded so far, the newest sits at `(head - 1) % RECENT_RESULTS_SIZE`
+    pub head: u64,
+    pub results: [RecentResult; RECENT_RESULTS_SIZE],
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl RecentResults {
+    pub fn push(&mut self, bet: &Bet, result: u32, slot: u64) {
+        let won = bet.bet_type.wins(result, bet.roll);
+        let index = (self.head % RECENT_RESULTS_SIZE as u64) as usize;
+        let (bet_type, high) = match bet.bet_type {
+            BetType::Over => (0, 0),
+            BetType::Under => (1, 0),
+            BetType::Range { high } => (2, high),
+            BetType::Exact => (3, 0),
+        };
+        self.results[index] = RecentResult {
+            player: bet.player,
+            mint: bet.mint,
+            seed: bet.seed.to_le_bytes(),
+            amount: bet.amount,
+            payout: if won { bet.payout } else { 0 },
+            slot,
+            target: bet.roll,
+            high,
+            roll: result as u16,
+            bet_type,
+            won: won as u8,
+        };
+        self.head += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ResolutionMode;
+
+    #[test]
+    fn records_the_bet_type_and_range() {
+        let bet = Bet {
+            player: Pubkey::new_unique(),
+            mint: Pubkey::default(),
+            seed: 1,
+            client_seed: [0; 32],
+            slot: 0,
+            target_slot: 0,
+            amount: 100,
+            payout: 180,
+            locked: 180,
+            roll: 4000,
+            bet_type: BetType::Range { high: 6000 },
+            mode: ResolutionMode::Signature,
+            chain_position: 0,
+            resolver: Pubkey::default(),
+            bump: 0,
+        };
+        let mut recent: RecentResults = bytemuck::Zeroable::zeroed();
+        let mint = Pubkey::new_unique();
+        recent.push(&bet, 5000, 10);
+        recent.push(
+            &Bet {
+                bet_type: BetType::Under,
+                mint,
+                ..bet.clone()
+            },
+            5000,
+            11,
+        );
+
+        let [range, under] = [recent.results[0], recent.results[1]];
+        assert_eq!((range.bet_type, range.high, range.won), (2, 6000, 1));
+        assert_eq!(range.payout, 180);
+        assert_eq!((under.bet_type, under.high, under.won), (1, 0, 0));
+        assert_eq!((range.mint, under.mint), (Pubkey::default(), mint));
+        assert_eq!(recent.head, 2);
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DiceError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenVault {
+    pub mint: Pubkey,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    // Sum of the payouts reserved by every open bet in this mint
+    pub locked: u64,
+    pub bump: u8,
+}
+
+impl TokenVault {
+    pub fn update(&mut self, min_bet: u64, max_bet: u64) -> Result<()> {
+        require!(min_bet <= max_bet, DiceError::InvalidConfig);
+
+        self.min_bet = min_bet;
+        self.max_bet = max_bet;
+        Ok(())
+    }
+
+    pub fn free_balance(&self, vault_amount: u64) -> u64 {
+        vault_amount.saturating_sub(self.locked)
+    }
+
+    pub fn lock(&mut self, amount: u64) -> Result<()> {
+        self.locked = self.locked.checked_add(amount).ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.locked = self.locked.checked_sub(amount).ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+}
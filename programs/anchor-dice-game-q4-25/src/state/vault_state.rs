@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+
+use solana_program::hash::hash;
+
+use crate::errors::DiceError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState {
+    // Sum of the payouts reserved by every open bet
+    pub locked: u64,
+    // Latest revealed server seed, or the committed tip before any reveal
+    pub chain_tip: [u8; 32],
+    // How many seeds below the committed tip `chain_tip` is
+    pub chain_position: u64,
+    // Position handed to the next hash-chain bet, 0 until a chain is committed
+    pub next_chain_position: u64,
+    // Hash-chain bets still waiting on their seed
+    pub open_chain_bets: u64,
+    pub bump: u8,
+}
+
+impl VaultState {
+    // Lamports left to back new bets once every pending payout and the rent-exempt minimum
+    // are set aside
+    pub fn free_balance(&self, vault_lamports: u64) -> Result<u64> {
+        let reserve = self
+            .locked
+            .checked_add(Rent::get()?.minimum_balance(0))
+            .ok_or(DiceError::Overflow)?;
+        Ok(vault_lamports.saturating_sub(reserve))
+    }
+
+    pub fn lock(&mut self, amount: u64) -> Result<()> {
+        self.locked = self.locked.checked_add(amount).ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.locked = self.locked.checked_sub(amount).ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    // Replacing the chain under open bets would let the house pick their seeds after seeing them
+    pub fn commit(&mut self, tip: [u8; 32]) -> Result<()> {
+        require!(self.open_chain_bets == 0, DiceError::HashChainBetsOpen);
+        self.chain_tip = tip;
+        self.chain_position = 0;
+        self.next_chain_position = 1;
+        Ok(())
+    }
+
+    // Each hash-chain bet owns one seed of the chain, so the house can't choose which bet a
+    // seed resolves
+    pub fn take_chain_position(&mut self) -> Result<u64> {
+        require!(
+            self.next_chain_position != 0,
+            DiceError::HashChainNotCommitted
+        );
+        let position = self.next_chain_position;
+        self.next_chain_position += 1;
+        self.open_chain_bets += 1;
+        Ok(position)
+    }
+
+    // Called when a hash-chain bet is resolved or refunded
+    pub fn close_chain_bet(&mut self) -> Result<()> {
+        self.open_chain_bets = self
+            .open_chain_bets
+            .checked_sub(1)
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    // Accepts the server seed at `position`. A seed past the tip must hash down to it and
+    // becomes the new tip, while an earlier one is checked against the tip, since revealing
+    // a seed also reveals every seed before it.
+    pub fn reveal(&mut self, position: u64, preimage: &[u8; 32]) -> Result<()> {
+        if position > self.chain_position {
+            require!(
+                hash_times(*preimage, position - self.chain_position) == self.chain_tip,
+                DiceError::HashChainPreimage
+            );
+            self.chain_tip = *preimage;
+            self.chain_position = position;
+        } else {
+            require!(
+                hash_times(self.chain_tip, self.chain_position - position) == *preimage,
+                DiceError::HashChainPreimage
+            );
+        }
+        Ok(())
+    }
+}
+
+fn hash_times(mut value: [u8; 32], times: u64) -> [u8; 32] {
+    for _ in 0..times {
+        value = hash(&value).to_bytes();
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault_state(seeds: &[[u8; 32]]) -> VaultState {
+        let mut state = VaultState {
+            locked: 0,
+            chain_tip: [0; 32],
+            chain_position: 0,
+            next_chain_position: 0,
+            open_chain_bets: 0,
+            bump: 0,
+        };
+        state.commit(hash(&seeds[0]).to_bytes()).unwrap();
+        state
+    }
+
+    // seeds[i] is the seed at position i + 1
+    fn chain(len: usize) -> Vec<[u8; 32]> {
+        let mut seeds = vec![[7u8; 32]];
+        for _ in 1..len {
+            seeds.insert(0, hash(&seeds[0]).to_bytes());
+        }
+        seeds
+    }
+
+    #[test]
+    fn binds_each_seed_to_its_position() {
+        let seeds = chain(3);
+        let mut state = vault_state(&seeds);
+
+        // A seed can't resolve a bet at another position
+        assert!(state.reveal(2, &seeds[0]).is_err());
+        assert!(state.reveal(1, &seeds[1]).is_err());
+
+        state.reveal(1, &seeds[0]).unwrap();
+        assert_eq!(state.chain_tip, seeds[0]);
+        assert!(state.reveal(2, &seeds[0]).is_err());
+    }
+
+    #[test]
+    fn resolves_positions_out_of_order() {
+        let seeds = chain(3);
+        let mut state = vault_state(&seeds);
+
+        state.reveal(3, &seeds[2]).unwrap();
+        assert_eq!(state.chain_position, 3);
+
+        // Earlier seeds follow from the revealed one and leave the tip alone
+        state.reveal(1, &seeds[0]).unwrap();
+        state.reveal(2, &seeds[1]).unwrap();
+        assert!(state.reveal(2, &seeds[0]).is_err());
+        assert_eq!(state.chain_tip, seeds[2]);
+    }
+
+    #[test]
+    fn keeps_the_chain_while_bets_are_open() {
+        let seeds = chain(2);
+        let mut state = vault_state(&seeds);
+        state.take_chain_position().unwrap();
+
+        assert_eq!(
+            state.commit([1; 32]).unwrap_err(),
+            DiceError::HashChainBetsOpen.into()
+        );
+
+        state.close_chain_bet().unwrap();
+        state.commit([1; 32]).unwrap();
+        assert_eq!(state.take_chain_position().unwrap(), 1);
+    }
+}
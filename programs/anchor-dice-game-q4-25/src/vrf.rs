@@ -0,0 +1,222 @@
+use sha2::{Digest, Sha512};
+use solana_curve25519::{
+    edwards::{multiply_edwards, subtract_edwards, validate_edwards, PodEdwardsPoint},
+    scalar::PodScalar,
+};
+
+// ECVRF-EDWARDS25519-SHA512-TAI from RFC 9381
+const SUITE_STRING: u8 = 0x03;
+const COFACTOR: u8 = 8;
+
+pub const PROOF_LEN: usize = 80;
+
+const BASEPOINT: PodEdwardsPoint = PodEdwardsPoint([
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+]);
+
+/// Verifies an ECVRF proof by `public_key` over `alpha` and returns the 64-byte VRF output.
+///
+/// The key is an ordinary Ed25519 public key, so the house can prove with its existing
+/// keypair. Unlike a signature, only one valid proof exists per key and message, so the
+/// house cannot grind for a favourable outcome on bets placed for VRF resolution.
+pub fn verify_vrf(
+    public_key: &[u8; 32],
+    proof: &[u8; PROOF_LEN],
+    alpha: &[u8],
+) -> Option<[u8; 64]> {
+    let y = PodEdwardsPoint(*public_key);
+    // Small-order keys would let many proofs verify
+    if multiply_edwards(&scalar(COFACTOR), &y)? == identity() {
+        return None;
+    }
+
+    let gamma = PodEdwardsPoint(proof[..32].try_into().unwrap());
+    if !validate_edwards(&gamma) {
+        return None;
+    }
+    let mut c = [0u8; 32];
+    c[..16].copy_from_slice(&proof[32..48]);
+    let c = PodScalar(c);
+    // Non-canonical `s` is rejected by the scalar multiplication below
+    let s = PodScalar(proof[48..].try_into().unwrap());
+
+    let h = encode_to_curve(public_key, alpha)?;
+
+    // U = s*B - c*Y, V = s*H - c*Gamma
+    let u = subtract_edwards(
+        &multiply_edwards(&s, &BASEPOINT)?,
+        &multiply_edwards(&c, &y)?,
+    )?;
+    let v = subtract_edwards(&multiply_edwards(&s, &h)?, &multiply_edwards(&c, &gamma)?)?;
+
+    if challenge(&y, &h, &gamma, &u, &v) != proof[32..48] {
+        return None;
+    }
+
+    proof_to_hash(&gamma)
+}
+
+pub(crate) fn encode_to_curve(public_key: &[u8; 32], alpha: &[u8]) -> Option<PodEdwardsPoint> {
+    // Try-and-increment: hash until the first 32 bytes decode to a curve point
+    for ctr in 0..=u8::MAX {
+        let digest = Sha512::new()
+            .chain_update([SUITE_STRING, 0x01])
+            .chain_update(public_key)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+
+        let candidate = PodEdwardsPoint(digest[..32].try_into().unwrap());
+        if validate_edwards(&candidate) {
+            return multiply_edwards(&scalar(COFACTOR), &candidate);
+        }
+    }
+    None
+}
+
+pub(crate) fn challenge(
+    y: &PodEdwardsPoint,
+    h: &PodEdwardsPoint,
+    gamma: &PodEdwardsPoint,
+    u: &PodEdwardsPoint,
+    v: &PodEdwardsPoint,
+) -> [u8; 16] {
+    let digest = Sha512::new()
+        .chain_update([SUITE_STRING, 0x02])
+        .chain_update(y.0)
+        .chain_update(h.0)
+        .chain_update(gamma.0)
+        .chain_update(u.0)
+        .chain_update(v.0)
+        .chain_update([0x00])
+        .finalize();
+
+    digest[..16].try_into().unwrap()
+}
+
+fn proof_to_hash(gamma: &PodEdwardsPoint) -> Option<[u8; 64]> {
+    let gamma = multiply_edwards(&scalar(COFACTOR), gamma)?;
+    let digest = Sha512::new()
+        .chain_update([SUITE_STRING, 0x03])
+        .chain_update(gamma.0)
+        .chain_update([0x00])
+        .finalize();
+
+    Some(digest.into())
+}
+
+fn scalar(value: u8) -> PodScalar {
+    let mut bytes = [0u8; 32];
+    bytes[0] = value;
+    PodScalar(bytes)
+}
+
+fn identity() -> PodEdwardsPoint {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 1;
+    PodEdwardsPoint(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    fn point(pod: &PodEdwardsPoint) -> EdwardsPoint {
+        EdwardsPoint::try_from(pod).unwrap()
+    }
+
+    // RFC 9381 section 5.1, as the house would run it off-chain
+    fn prove(secret_key: &[u8; 32], alpha: &[u8]) -> ([u8; 32], [u8; PROOF_LEN]) {
+        let expanded = Sha512::digest(secret_key);
+        let mut x_bytes: [u8; 32] = expanded[..32].try_into().unwrap();
+        x_bytes[0] &= 248;
+        x_bytes[31] &= 127;
+        x_bytes[31] |= 64;
+        let x = Scalar::from_bytes_mod_order(x_bytes);
+        let public_key = EdwardsPoint::mul_base(&x).compress().to_bytes();
+
+        let h = encode_to_curve(&public_key, alpha).unwrap();
+        let gamma = x * point(&h);
+
+        let k_digest = Sha512::new()
+            .chain_update(&expanded[32..])
+            .chain_update(h.0)
+            .finalize();
+        let k = Scalar::from_bytes_mod_order_wide(&k_digest.into());
+
+        let c_bytes = challenge(
+            &PodEdwardsPoint(public_key),
+            &h,
+            &PodEdwardsPoint::from(&gamma),
+            &PodEdwardsPoint::from(&(k * ED25519_BASEPOINT_POINT)),
+            &PodEdwardsPoint::from(&(k * point(&h))),
+        );
+        let mut c_wide = [0u8; 32];
+        c_wide[..16].copy_from_slice(&c_bytes);
+        let s = k + Scalar::from_bytes_mod_order(c_wide) * x;
+
+        let mut proof = [0u8; PROOF_LEN];
+        proof[..32].copy_from_slice(gamma.compress().as_bytes());
+        proof[32..48].copy_from_slice(&c_bytes);
+        proof[48..].copy_from_slice(s.as_bytes());
+        (public_key, proof)
+    }
+
+    #[test]
+    fn matches_rfc_9381_test_vector() {
+        // Appendix B.3, example 16
+        let public_key =
+            hex::<32>("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let proof = hex::<80>("8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805");
+        let beta = hex::<64>("90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae");
+
+        assert_eq!(verify_vrf(&public_key, &proof, &[]), Some(beta));
+
+        let secret_key =
+            hex::<32>("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        assert_eq!(prove(&secret_key, &[]), (public_key, proof));
+    }
+
+    #[test]
+    fn proves_and_verifies() {
+        let secret_key =
+            hex::<32>("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let alpha = b"bet message";
+
+        let (public_key, proof) = prove(&secret_key, alpha);
+        assert!(verify_vrf(&public_key, &proof, alpha).is_some());
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let secret_key = [42u8; 32];
+        let alpha = b"bet message";
+        let (public_key, proof) = prove(&secret_key, alpha);
+
+        assert!(verify_vrf(&public_key, &proof, b"other message").is_none());
+        for index in [0, 40, 60] {
+            let mut tampered = proof;
+            tampered[index] ^= 1;
+            assert!(verify_vrf(&public_key, &tampered, alpha).is_none());
+        }
+
+        let (other_key, _) = prove(&[7u8; 32], alpha);
+        assert!(verify_vrf(&other_key, &proof, alpha).is_none());
+    }
+
+    #[test]
+    fn rejects_small_order_keys() {
+        let (_, proof) = prove(&[42u8; 32], b"bet message");
+        assert!(verify_vrf(&identity().0, &proof, b"bet message").is_none());
+    }
+}
